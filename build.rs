@@ -39,7 +39,7 @@ fn main() {
         .generate_comments(true)
         .use_core()
         .allowlist_type("io_uring_.*|io_.qring_.*|__kernel_timespec|open_how")
-        .allowlist_var("__NR_io_uring.*|IOSQE_.*|IORING_.*|IO_URING_.*|SPLICE_F_FD_IN_FIXED")
+        .allowlist_var("__NR_io_uring.*|IOSQE_.*|IORING_.*|IO_URING_.*|SPLICE_F_FD_IN_FIXED|SOCKET_URING_OP_.*|IOU_.*")
         .generate()
         .unwrap()
         .write_to_file(outdir.join("sys.rs"))
@@ -13,33 +13,57 @@ impl TaskQueue {
     }
 }
 
+fn submit_nops(io_uring: &mut IoUring) {
+    let mut queue = TaskQueue(128);
+
+    while queue.want() {
+        {
+            let mut sq = io_uring.submission();
+            while queue.want() {
+                unsafe {
+                    match sq.push(&black_box(opcode::Nop::new()).build()) {
+                        Ok(_) => queue.pop(),
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+
+        io_uring.submit_and_wait(16).unwrap();
+
+        io_uring.completion().map(black_box).for_each(drop);
+    }
+}
+
 fn bench_normal(c: &mut Criterion) {
     let mut io_uring = IoUring::new(16).unwrap();
 
     c.bench_function("normal", |b| {
-        b.iter(|| {
-            let mut queue = TaskQueue(128);
+        b.iter(|| submit_nops(&mut io_uring));
+    });
+}
 
-            while queue.want() {
-                {
-                    let mut sq = io_uring.submission();
-                    while queue.want() {
-                        unsafe {
-                            match sq.push(&black_box(opcode::Nop::new()).build()) {
-                                Ok(_) => queue.pop(),
-                                Err(_) => break,
-                            }
-                        }
-                    }
-                }
+fn bench_registered_ring_fd(c: &mut Criterion) {
+    let mut io_uring = IoUring::new(16).unwrap();
+    io_uring.submitter().register_ring_fd().unwrap();
 
-                io_uring.submit_and_wait(16).unwrap();
+    c.bench_function("registered_ring_fd", |b| {
+        b.iter(|| submit_nops(&mut io_uring));
+    });
+}
 
-                io_uring.completion().map(black_box).for_each(drop);
-            }
-        });
+fn bench_no_sqarray(c: &mut Criterion) {
+    let mut io_uring = IoUring::builder().setup_no_sqarray().build(16).unwrap();
+
+    c.bench_function("no_sqarray", |b| {
+        b.iter(|| submit_nops(&mut io_uring));
     });
 }
 
-criterion_group!(squeue, bench_normal);
+criterion_group!(
+    squeue,
+    bench_normal,
+    bench_registered_ring_fd,
+    bench_no_sqarray
+);
 criterion_main!(squeue);
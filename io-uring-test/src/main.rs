@@ -71,11 +71,17 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     tests::queue::test_debug_print(&mut ring, &test)?;
     tests::queue::test_msg_ring_data(&mut ring, &test)?;
     tests::queue::test_msg_ring_send_fd(&mut ring, &test)?;
+    tests::queue::test_resize(&mut ring, &test)?;
+    tests::queue::test_setup_no_mmap(&mut ring, &test)?;
+    tests::queue::test_setup_no_sqarray(&mut ring, &test)?;
 
     tests::queue::test_batch(&mut ring, &test)?;
 
     // register
     tests::register::test_register_files_sparse(&mut ring, &test)?;
+    tests::register::test_register_files_tags(&mut ring, &test)?;
+    tests::register::test_register_ring_fd(&mut ring, &test)?;
+    tests::register::test_register_iowq_aff(&mut ring, &test)?;
     tests::register_buffers::test_register_buffers(&mut ring, &test)?;
     tests::register_buffers::test_register_buffers_update_tag(&mut ring, &test)?;
     tests::register_buf_ring::test_register_buf_ring(&mut ring, &test)?;
@@ -88,6 +94,7 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     tests::fs::test_file_fsync_file_range(&mut ring, &test)?;
     tests::fs::test_file_fallocate(&mut ring, &test)?;
     tests::fs::test_file_fallocate64(&mut ring, &test)?;
+    tests::fs::test_file_ftruncate(&mut ring, &test)?;
     tests::fs::test_file_openat2(&mut ring, &test)?;
     tests::fs::test_file_openat2_close_file_index(&mut ring, &test)?;
     tests::fs::test_file_openat_close_file_index(&mut ring, &test)?;
@@ -97,6 +104,8 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     #[cfg(not(feature = "ci"))]
     tests::fs::test_statx(&mut ring, &test)?;
     tests::fs::test_file_splice(&mut ring, &test)?;
+    tests::fs::test_file_read_multi(&mut ring, &test)?;
+    tests::fs::test_file_xattr(&mut ring, &test)?;
 
     // timeout
     tests::timeout::test_timeout(&mut ring, &test)?;
@@ -105,11 +114,14 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     tests::timeout::test_timeout_cancel(&mut ring, &test)?;
     tests::timeout::test_timeout_abs(&mut ring, &test)?;
     tests::timeout::test_timeout_submit_args(&mut ring, &test)?;
+    tests::timeout::test_timeout_update(&mut ring, &test)?;
+    tests::timeout::test_link_timeout_update(&mut ring, &test)?;
 
     // net
     tests::net::test_tcp_write_read(&mut ring, &test)?;
     tests::net::test_tcp_writev_readv(&mut ring, &test)?;
     tests::net::test_tcp_send_recv(&mut ring, &test)?;
+    tests::net::test_tcp_send_recv_poll_first(&mut ring, &test)?;
     tests::net::test_tcp_zero_copy_send_recv(&mut ring, &test)?;
     tests::net::test_tcp_zero_copy_send_fixed(&mut ring, &test)?;
     tests::net::test_tcp_sendmsg_recvmsg(&mut ring, &test)?;
@@ -120,6 +132,8 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     tests::net::test_tcp_accept_multi(&mut ring, &test)?;
     #[cfg(not(feature = "ci"))]
     tests::net::test_tcp_accept_multi_file_index(&mut ring, &test)?;
+    tests::net::test_tcp_accept_multi_file_index_alloc_range(&mut ring, &test)?;
+    tests::net::test_tcp_fixed_fd_install(&mut ring, &test)?;
     tests::net::test_tcp_connect(&mut ring, &test)?;
     tests::net::test_tcp_buffer_select(&mut ring, &test)?;
     #[cfg(not(feature = "ci"))]
@@ -128,9 +142,12 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     tests::net::test_tcp_buffer_select_readv(&mut ring, &test)?;
     #[cfg(not(feature = "ci"))]
     tests::net::test_tcp_recv_multi(&mut ring, &test)?;
+    tests::net::test_tcp_recv_send_bundle(&mut ring, &test)?;
     tests::net::test_shutdown(&mut ring, &test)?;
     tests::net::test_socket(&mut ring, &test)?;
+    tests::net::test_socket_cmd(&mut ring, &test)?;
     tests::net::test_udp_recvmsg_multishot(&mut ring, &test)?;
+    tests::net::test_register_napi(&mut ring, &test)?;
 
     // queue
     tests::poll::test_eventfd_poll(&mut ring, &test)?;
@@ -138,6 +155,22 @@ fn test<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     tests::poll::test_eventfd_poll_remove_failed(&mut ring, &test)?;
     #[cfg(not(feature = "ci"))]
     tests::poll::test_eventfd_poll_multi(&mut ring, &test)?;
+    tests::poll::test_eventfd_poll_update(&mut ring, &test)?;
+    tests::poll::test_eventfd_poll_level(&mut ring, &test)?;
+
+    // process
+    tests::process::test_waitid(&mut ring, &test)?;
+
+    // futex
+    tests::futex::test_futex_wait(&mut ring, &test)?;
+    tests::futex::test_futex_wake(&mut ring, &test)?;
+    tests::futex::test_futex_waitv(&mut ring, &test)?;
+
+    // cancel
+    tests::cancel::test_async_cancel_user_data_all(&mut ring, &test)?;
+    tests::cancel::test_async_cancel_fd(&mut ring, &test)?;
+    tests::cancel::test_async_cancel_any(&mut ring, &test)?;
+    tests::cancel::test_register_sync_cancel(&mut ring, &test)?;
 
     // regression test
     tests::regression::test_issue154(&mut ring, &test)?;
@@ -0,0 +1,399 @@
+use crate::Test;
+use io_uring::types::CancelBuilder;
+use io_uring::{cqueue, opcode, squeue, types, IoUring};
+use std::fs::File;
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::thread;
+use std::time::Duration;
+
+fn eventfd() -> io::Result<File> {
+    unsafe {
+        let fd = libc::eventfd(0, libc::EFD_CLOEXEC);
+
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(File::from_raw_fd(fd))
+    }
+}
+
+pub fn test_async_cancel_user_data_all<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::PollAdd::CODE);
+        test.probe.is_supported(opcode::AsyncCancel2::CODE);
+        test.probe.is_supported(opcode::Socket::CODE); // cancel flags are available since 5.19
+    );
+
+    println!("test async_cancel_user_data_all");
+
+    let fd = eventfd()?;
+    let fd = types::Fd(fd.as_raw_fd());
+
+    // add two polls sharing the same user_data
+
+    let poll_e = opcode::PollAdd::new(fd, libc::POLLIN as _).build();
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&poll_e.clone().user_data(0x20).into())
+            .expect("queue is full");
+        queue
+            .push(&poll_e.clone().user_data(0x20).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    // cancel both of them
+
+    let cancel_e = opcode::AsyncCancel2::new(CancelBuilder::user_data(0x20).all());
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.build().user_data(0x21).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(3)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 3);
+    assert_eq!(cqes[0].user_data(), 0x20);
+    assert_eq!(cqes[1].user_data(), 0x20);
+    assert_eq!(cqes[2].user_data(), 0x21);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), -libc::ECANCELED);
+    assert_eq!(cqes[2].result(), 2);
+
+    Ok(())
+}
+
+pub fn test_async_cancel_fd<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::PollAdd::CODE);
+        test.probe.is_supported(opcode::AsyncCancel2::CODE);
+        test.probe.is_supported(opcode::SendZc::CODE); // fixed fd matching is available since 6.0
+    );
+
+    println!("test async_cancel_fd");
+
+    let fd = eventfd()?;
+    let fd2 = eventfd()?;
+
+    // cancel every request on a normal fd, leaving the other fd alone
+
+    let poll_e = opcode::PollAdd::new(types::Fd(fd.as_raw_fd()), libc::POLLIN as _).build();
+    let poll2_e = opcode::PollAdd::new(types::Fd(fd2.as_raw_fd()), libc::POLLIN as _).build();
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&poll_e.clone().user_data(0x22).into())
+            .expect("queue is full");
+        queue
+            .push(&poll_e.clone().user_data(0x23).into())
+            .expect("queue is full");
+        queue
+            .push(&poll2_e.clone().user_data(0x24).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    let cancel_e = opcode::AsyncCancel2::new(CancelBuilder::fd(types::Fd(fd.as_raw_fd())).all());
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.build().user_data(0x25).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(3)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 3);
+    assert_eq!(cqes[0].user_data(), 0x22);
+    assert_eq!(cqes[1].user_data(), 0x23);
+    assert_eq!(cqes[2].user_data(), 0x25);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), -libc::ECANCELED);
+    assert_eq!(cqes[2].result(), 2);
+
+    // cancel the remaining request through a fixed fd
+
+    let _ = ring.submitter().unregister_files();
+    ring.submitter().register_files(&[fd2.as_raw_fd()])?;
+
+    let poll_e = opcode::PollAdd::new(types::Fixed(0), libc::POLLIN as _).build();
+
+    unsafe {
+        ring.submission()
+            .push(&poll_e.clone().user_data(0x26).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    let cancel_e = opcode::AsyncCancel2::new(CancelBuilder::fd(types::Fixed(0)));
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.build().user_data(0x27).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x26);
+    assert_eq!(cqes[1].user_data(), 0x27);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), 0);
+
+    // the request issued against the normal fd is still pending
+
+    let cancel_e = opcode::AsyncCancel2::new(CancelBuilder::user_data(0x24));
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.build().user_data(0x28).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x24);
+    assert_eq!(cqes[1].user_data(), 0x28);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), 0);
+
+    ring.submitter().unregister_files()?;
+
+    Ok(())
+}
+
+pub fn test_async_cancel_any<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    _ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::PollAdd::CODE);
+        test.probe.is_supported(opcode::AsyncCancel2::CODE);
+        test.probe.is_supported(opcode::Socket::CODE); // cancel flags are available since 5.19
+    );
+
+    println!("test async_cancel_any");
+
+    // Cancelling any request would also catch requests left in flight on the shared ring.
+    let mut ring = IoUring::<S, C>::generic_builder().build(8)?;
+
+    let fd = eventfd()?;
+    let fd2 = eventfd()?;
+
+    let poll_e = opcode::PollAdd::new(types::Fd(fd.as_raw_fd()), libc::POLLIN as _).build();
+    let poll2_e = opcode::PollAdd::new(types::Fd(fd2.as_raw_fd()), libc::POLLIN as _).build();
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&poll_e.clone().user_data(0x29).into())
+            .expect("queue is full");
+        queue
+            .push(&poll2_e.clone().user_data(0x2a).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    let cancel_e = opcode::AsyncCancel2::new(CancelBuilder::any()).build();
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.clone().user_data(0x2b).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(3)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 3);
+    assert_eq!(cqes[0].user_data(), 0x29);
+    assert_eq!(cqes[1].user_data(), 0x2a);
+    assert_eq!(cqes[2].user_data(), 0x2b);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), -libc::ECANCELED);
+    assert_eq!(cqes[2].result(), 2);
+
+    // nothing left to cancel
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.user_data(0x2c).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x2c);
+    assert_eq!(cqes[0].result(), 0);
+
+    Ok(())
+}
+
+pub fn test_register_sync_cancel<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::PollAdd::CODE);
+        test.probe.is_supported(opcode::SendZc::CODE); // sync cancel is available since 6.0
+    );
+
+    println!("test register_sync_cancel");
+
+    let fd = eventfd()?;
+    let fd2 = eventfd()?;
+
+    let poll_e = opcode::PollAdd::new(types::Fd(fd.as_raw_fd()), libc::POLLIN as _).build();
+    let poll2_e = opcode::PollAdd::new(types::Fd(fd2.as_raw_fd()), libc::POLLIN as _).build();
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&poll_e.clone().user_data(0x30).into())
+            .expect("queue is full");
+        queue
+            .push(&poll_e.user_data(0x31).into())
+            .expect("queue is full");
+        queue
+            .push(&poll2_e.user_data(0x32).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    // by user_data, waiting indefinitely
+
+    ring.submitter()
+        .register_sync_cancel(None, CancelBuilder::user_data(0x30))?;
+
+    // by fd, with a timeout
+
+    let ts = types::Timespec::new().sec(1);
+    ring.submitter().register_sync_cancel(
+        Some(ts),
+        CancelBuilder::fd(types::Fd(fd2.as_raw_fd())).all(),
+    )?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x30);
+    assert_eq!(cqes[1].user_data(), 0x32);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), -libc::ECANCELED);
+
+    // nothing left to match
+
+    let err = ring
+        .submitter()
+        .register_sync_cancel(None, CancelBuilder::user_data(0x30))
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    // cleanup, without touching requests of other tests
+
+    ring.submitter()
+        .register_sync_cancel(None, CancelBuilder::user_data(0x31))?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x31);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+
+    // timeout: a splice from an empty pipe blocks in an io-wq worker, which is only interrupted
+    // once the cancellation reaches it, well after an immediate timeout.
+
+    if test.probe.is_supported(opcode::Splice::CODE) {
+        let (pipe_r, pipe_w) = {
+            let mut fds = [0; 2];
+            if unsafe { libc::pipe(fds.as_mut_ptr()) } == -1 {
+                return Err(io::Error::last_os_error().into());
+            }
+            unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) }
+        };
+        let null = File::options().write(true).open("/dev/null")?;
+
+        let splice_e = opcode::Splice::new(
+            types::Fd(pipe_r.as_raw_fd()),
+            -1,
+            types::Fd(null.as_raw_fd()),
+            -1,
+            16,
+        );
+
+        unsafe {
+            ring.submission()
+                .push(&splice_e.build().user_data(0x33).into())
+                .expect("queue is full");
+        }
+
+        ring.submit()?;
+        thread::sleep(Duration::from_millis(10));
+
+        let err = ring
+            .submitter()
+            .register_sync_cancel(Some(types::Timespec::new()), CancelBuilder::user_data(0x33))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        let inner = err
+            .get_ref()
+            .and_then(|err| err.downcast_ref::<io::Error>());
+        assert_eq!(inner.and_then(io::Error::raw_os_error), Some(libc::ETIME));
+
+        // the splice still completes once interrupted
+
+        ring.submit_and_wait(1)?;
+
+        let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+        assert_eq!(cqes.len(), 1);
+        assert_eq!(cqes[0].user_data(), 0x33);
+
+        drop(pipe_w);
+    }
+
+    Ok(())
+}
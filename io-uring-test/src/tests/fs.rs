@@ -1,6 +1,6 @@
 use crate::utils;
 use crate::Test;
-use io_uring::{cqueue, opcode, squeue, types, IoUring};
+use io_uring::{buf_ring, cqueue, opcode, squeue, types, IoUring};
 use std::ffi::CString;
 use std::fs;
 use std::io::Write;
@@ -188,6 +188,65 @@ pub fn test_file_fallocate64<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     Ok(())
 }
 
+pub fn test_file_ftruncate<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::Ftruncate::CODE);
+    );
+
+    println!("test file_ftruncate");
+
+    let file = tempfile::tempfile()?;
+    file.set_len(4096)?;
+
+    // Truncate through a regular descriptor, then through a fixed one.
+    let fd = types::Fd(file.as_raw_fd());
+    let ftruncate_e = opcode::Ftruncate::new(fd, 1024);
+
+    unsafe {
+        ring.submission()
+            .push(&ftruncate_e.build().user_data(0x30).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x30);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(file.metadata()?.len(), 1024);
+
+    // Cleanup all fixed files (if any), then register the file in slot 0.
+    let _ = ring.submitter().unregister_files();
+    ring.submitter().register_files(&[file.as_raw_fd()])?;
+
+    let ftruncate_e = opcode::Ftruncate::new(types::Fixed(0), 0);
+
+    unsafe {
+        ring.submission()
+            .push(&ftruncate_e.build().user_data(0x31).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x31);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(file.metadata()?.len(), 0);
+
+    ring.submitter().unregister_files()?;
+
+    Ok(())
+}
+
 pub fn test_file_openat2<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     ring: &mut IoUring<S, C>,
     test: &Test,
@@ -844,3 +903,213 @@ pub fn test_file_splice<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
 
     Ok(())
 }
+
+pub fn test_file_read_multi<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::ReadMulti::CODE);
+    );
+
+    println!("test file_read_multi");
+
+    let (mut pipe_in, pipe_out) = {
+        let mut pipes = [0, 0];
+        let ret = unsafe { libc::pipe(pipes.as_mut_ptr()) };
+        assert_eq!(ret, 0);
+        let pipe_out = unsafe { fs::File::from_raw_fd(pipes[0]) };
+        let pipe_in = unsafe { fs::File::from_raw_fd(pipes[1]) };
+        (pipe_in, pipe_out)
+    };
+
+    let buf_ring = buf_ring::Builder::new(0xdead)
+        .ring_entries(4)
+        .buf_len(64)
+        .build(&ring.submitter())?;
+
+    let read_e = opcode::ReadMulti::new(types::Fd(pipe_out.as_raw_fd()), buf_ring.bgid());
+
+    unsafe {
+        ring.submission()
+            .push(&read_e.build().user_data(0x34).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    // Each write is drained by the same request, in a buffer of its own.
+    let inputs: [&[u8]; 3] = [b"hello", b"io_uring", b"world"];
+    for input in inputs.iter() {
+        pipe_in.write_all(input)?;
+        ring.submit_and_wait(1)?;
+
+        let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+        assert_eq!(cqes.len(), 1);
+        assert_eq!(cqes[0].user_data(), 0x34);
+        assert_eq!(cqes[0].result(), input.len() as i32);
+        assert!(cqueue::more(cqes[0].flags()));
+
+        let buf = unsafe { buf_ring.get_buf(&cqes[0]) }.expect("no buffer selected");
+        assert_eq!(&buf[..], *input);
+    }
+
+    // Closing the write end of the pipe terminates the request.
+    drop(pipe_in);
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x34);
+    assert_eq!(cqes[0].result(), 0);
+    assert!(!cqueue::more(cqes[0].flags()));
+
+    Ok(())
+}
+
+pub fn test_file_xattr<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::SetXattr::CODE);
+        test.probe.is_supported(opcode::GetXattr::CODE);
+        test.probe.is_supported(opcode::FSetXattr::CODE);
+        test.probe.is_supported(opcode::FGetXattr::CODE);
+    );
+
+    println!("test file_xattr");
+
+    let file = tempfile::NamedTempFile::new_in(".")?;
+    let path = CString::new(file.path().as_os_str().as_bytes())?;
+    let fd = types::Fd(file.as_file().as_raw_fd());
+
+    // setxattr by path, fgetxattr by fd
+
+    let name = CString::new("user.io-uring-test")?;
+    let value = b"The quick brown fox jumps over the lazy dog.";
+    let mut output = vec![0; 64];
+
+    let setxattr_e = opcode::SetXattr::new(
+        name.as_ptr(),
+        value.as_ptr().cast(),
+        path.as_ptr(),
+        value.len() as _,
+    )
+    .flags(libc::XATTR_CREATE);
+    let fgetxattr_e = opcode::FGetXattr::new(
+        fd,
+        name.as_ptr(),
+        output.as_mut_ptr().cast(),
+        output.len() as _,
+    );
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(
+                &setxattr_e
+                    .build()
+                    .user_data(0x01)
+                    .flags(squeue::Flags::IO_LINK)
+                    .into(),
+            )
+            .expect("queue is full");
+        queue
+            .push(&fgetxattr_e.build().user_data(0x02).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[1].user_data(), 0x02);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(cqes[1].result(), value.len() as i32);
+    assert_eq!(&output[..value.len()], value);
+
+    // fsetxattr by fixed file, getxattr by path
+
+    let _ = ring.submitter().unregister_files();
+    ring.submitter().register_files(&[fd.0])?;
+
+    let name = CString::new("user.io-uring-test-fixed")?;
+    let value = "我能吞下玻璃而不伤身体。".as_bytes();
+    let mut output = vec![0; 64];
+
+    let fsetxattr_e = opcode::FSetXattr::new(
+        types::Fixed(0),
+        name.as_ptr(),
+        value.as_ptr().cast(),
+        value.len() as _,
+    );
+    let getxattr_e = opcode::GetXattr::new(
+        name.as_ptr(),
+        output.as_mut_ptr().cast(),
+        path.as_ptr(),
+        output.len() as _,
+    );
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(
+                &fsetxattr_e
+                    .build()
+                    .user_data(0x03)
+                    .flags(squeue::Flags::IO_LINK)
+                    .into(),
+            )
+            .expect("queue is full");
+        queue
+            .push(&getxattr_e.build().user_data(0x04).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x03);
+    assert_eq!(cqes[1].user_data(), 0x04);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(cqes[1].result(), value.len() as i32);
+    assert_eq!(&output[..value.len()], value);
+
+    ring.submitter().unregister_files()?;
+
+    // missing attribute
+
+    let name = CString::new("user.io-uring-test-missing")?;
+
+    let getxattr_e = opcode::GetXattr::new(
+        name.as_ptr(),
+        output.as_mut_ptr().cast(),
+        path.as_ptr(),
+        output.len() as _,
+    );
+
+    unsafe {
+        ring.submission()
+            .push(&getxattr_e.build().user_data(0x05).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x05);
+    assert_eq!(cqes[0].result(), -libc::ENODATA);
+
+    Ok(())
+}
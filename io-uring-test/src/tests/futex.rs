@@ -0,0 +1,228 @@
+use crate::Test;
+use io_uring::{cqueue, opcode, squeue, types, IoUring};
+use std::io;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+// Not yet exported by libc.
+const FUTEX2_SIZE_U32: u32 = 0x02;
+const FUTEX2_PRIVATE: u32 = libc::FUTEX_PRIVATE_FLAG as u32;
+const FUTEX_BITSET_MATCH_ANY: u64 = u32::MAX as u64;
+
+pub fn test_futex_wait<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::FutexWait::CODE);
+    );
+
+    println!("test futex_wait");
+
+    let futex = Arc::new(AtomicU32::new(0));
+    let (tx, rx) = mpsc::channel::<()>();
+
+    // Spawn the waker before arming the wait. On recent kernels, spawning the first thread of a
+    // process can resize its private futex hash, and a wait armed before that is not found by
+    // the waker.
+    let waker = {
+        let futex = futex.clone();
+        thread::spawn(move || {
+            rx.recv().unwrap();
+            futex.store(1, Ordering::Release);
+            let ret = unsafe {
+                libc::syscall(
+                    libc::SYS_futex,
+                    futex.as_ptr(),
+                    libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+                    1,
+                )
+            };
+            if ret == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(ret)
+            }
+        })
+    };
+
+    let wait_e = opcode::FutexWait::new(
+        futex.as_ptr(),
+        0,
+        FUTEX_BITSET_MATCH_ANY,
+        FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+    );
+
+    unsafe {
+        ring.submission()
+            .push(&wait_e.build().user_data(0x01).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(ring.completion().len(), 0);
+
+    tx.send(()).unwrap();
+    ring.submit_and_wait(1)?;
+    assert_eq!(waker.join().unwrap()?, 1);
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[0].result(), 0);
+
+    // The futex no longer holds the expected value.
+    let wait_e = opcode::FutexWait::new(
+        futex.as_ptr(),
+        0,
+        FUTEX_BITSET_MATCH_ANY,
+        FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+    );
+
+    unsafe {
+        ring.submission()
+            .push(&wait_e.build().user_data(0x02).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x02);
+    assert_eq!(cqes[0].result(), -libc::EAGAIN);
+
+    Ok(())
+}
+
+pub fn test_futex_wake<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::FutexWait::CODE);
+        test.probe.is_supported(opcode::FutexWake::CODE);
+    );
+
+    println!("test futex_wake");
+
+    let futex = AtomicU32::new(0);
+
+    let wait_e = opcode::FutexWait::new(
+        futex.as_ptr(),
+        0,
+        FUTEX_BITSET_MATCH_ANY,
+        FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+    );
+
+    unsafe {
+        ring.submission()
+            .push(&wait_e.build().user_data(0x01).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(ring.completion().len(), 0);
+
+    futex.store(1, Ordering::Release);
+
+    let wake_e = opcode::FutexWake::new(
+        futex.as_ptr(),
+        1,
+        FUTEX_BITSET_MATCH_ANY,
+        FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+    );
+
+    unsafe {
+        ring.submission()
+            .push(&wake_e.build().user_data(0x02).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(cqes[1].user_data(), 0x02);
+    assert_eq!(cqes[1].result(), 1);
+
+    Ok(())
+}
+
+pub fn test_futex_waitv<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::FutexWaitV::CODE);
+        test.probe.is_supported(opcode::FutexWake::CODE);
+    );
+
+    println!("test futex_waitv");
+
+    let futexes = [AtomicU32::new(0), AtomicU32::new(0)];
+    let futexv = [
+        types::FutexWaitV::new()
+            .val(0)
+            .uaddr(futexes[0].as_ptr() as _)
+            .flags(FUTEX2_SIZE_U32 | FUTEX2_PRIVATE),
+        types::FutexWaitV::new()
+            .val(0)
+            .uaddr(futexes[1].as_ptr() as _)
+            .flags(FUTEX2_SIZE_U32 | FUTEX2_PRIVATE),
+    ];
+
+    let waitv_e = opcode::FutexWaitV::new(futexv.as_ptr(), futexv.len() as _);
+
+    unsafe {
+        ring.submission()
+            .push(&waitv_e.build().user_data(0x01).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(ring.completion().len(), 0);
+
+    // Wake the second futex, the completion reports its index.
+    futexes[1].store(1, Ordering::Release);
+
+    let wake_e = opcode::FutexWake::new(
+        futexes[1].as_ptr(),
+        1,
+        FUTEX_BITSET_MATCH_ANY,
+        FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+    );
+
+    unsafe {
+        ring.submission()
+            .push(&wake_e.build().user_data(0x02).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[0].result(), 1);
+    assert_eq!(cqes[1].user_data(), 0x02);
+    assert_eq!(cqes[1].result(), 1);
+
+    Ok(())
+}
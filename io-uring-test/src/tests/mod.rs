@@ -1,6 +1,9 @@
+pub mod cancel;
 pub mod fs;
+pub mod futex;
 pub mod net;
 pub mod poll;
+pub mod process;
 pub mod queue;
 pub mod register;
 pub mod register_buf_ring;
@@ -114,6 +114,61 @@ pub fn test_tcp_send_recv<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     Ok(())
 }
 
+pub fn test_tcp_send_recv_poll_first<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::Send::CODE);
+        test.probe.is_supported(opcode::Recv::CODE);
+        test.probe.is_supported(opcode::Socket::CODE); // 5.19, POLL_FIRST was added at the same time
+    );
+
+    println!("test tcp_send_recv_poll_first");
+
+    let (send_stream, recv_stream) = tcp_pair()?;
+
+    let send_fd = types::Fd(send_stream.as_raw_fd());
+    let recv_fd = types::Fd(recv_stream.as_raw_fd());
+
+    let text = b"The quick brown fox jumps over the lazy dog.";
+    let mut output = vec![0; text.len()];
+
+    // The receive is armed first, on a socket that is known to be empty.
+    let recv_e = opcode::Recv::new(recv_fd, output.as_mut_ptr(), output.len() as _)
+        .poll_first(true)
+        .build()
+        .user_data(0x01)
+        .into();
+    let send_e = opcode::Send::new(send_fd, text.as_ptr(), text.len() as _)
+        .poll_first(true)
+        .build()
+        .user_data(0x02)
+        .into();
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue.push(&recv_e).expect("queue is full");
+        queue.push(&send_e).expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[1].user_data(), 0x02);
+    assert_eq!(cqes[0].result(), text.len() as i32);
+    assert_eq!(cqes[1].result(), text.len() as i32);
+
+    assert_eq!(&output, text);
+
+    Ok(())
+}
+
 pub fn test_tcp_zero_copy_send_recv<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     ring: &mut IoUring<S, C>,
     test: &Test,
@@ -710,6 +765,177 @@ pub fn test_tcp_accept_multi_file_index<S: squeue::EntryMarker, C: cqueue::Entry
     Ok(())
 }
 
+pub fn test_tcp_accept_multi_file_index_alloc_range<
+    S: squeue::EntryMarker,
+    C: cqueue::EntryMarker,
+>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::Accept::CODE);
+        test.probe.is_supported(opcode::SendZc::CODE); // Available since 6.0, as is the file alloc range.
+    );
+
+    println!("test tcp_accept_multi_file_index_alloc_range");
+
+    let listener = TCP_LISTENER.get_or_try_init(|| TcpListener::bind("127.0.0.1:0"))?;
+    let addr = listener.local_addr()?;
+    let fd = types::Fd(listener.as_raw_fd());
+
+    // 2 streams
+
+    let _stream1 = TcpStream::connect(addr)?;
+    let _stream2 = TcpStream::connect(addr)?;
+
+    // 4 fixed table index spots, of which only the upper 2 are used for allocation
+
+    // Cleanup all fixed files (if any).
+    let _ = ring.submitter().unregister_files();
+
+    ring.submitter().register_files_sparse(4).unwrap();
+
+    // The range must fit in the table.
+    assert!(ring.submitter().register_file_alloc_range(2, 3).is_err());
+
+    ring.submitter().register_file_alloc_range(2, 2)?;
+
+    let accept_e = opcode::AcceptMulti::new(fd).allocate_file_index(true);
+
+    unsafe {
+        ring.submission()
+            .push(&accept_e.build().user_data(2004).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 2004);
+    assert_eq!(cqes[1].user_data(), 2004);
+
+    let mut slots = vec![cqes[0].result(), cqes[1].result()];
+    slots.sort_unstable();
+    assert_eq!(slots, vec![2, 3]);
+
+    // Cancel the multishot accept
+
+    let cancel_e = opcode::AsyncCancel::new(2004);
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.build().user_data(2005).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 2004);
+    assert_eq!(cqes[1].user_data(), 2005);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), 0);
+
+    ring.submitter().unregister_files().unwrap();
+
+    Ok(())
+}
+
+pub fn test_tcp_fixed_fd_install<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    use std::io::{Read, Write};
+
+    require!(
+        test;
+        test.probe.is_supported(opcode::Accept::CODE);
+        test.probe.is_supported(opcode::FixedFdInstall::CODE);
+    );
+
+    println!("test tcp_fixed_fd_install");
+
+    let listener = TCP_LISTENER.get_or_try_init(|| TcpListener::bind("127.0.0.1:0"))?;
+    let addr = listener.local_addr()?;
+    let fd = types::Fd(listener.as_raw_fd());
+
+    let mut client = TcpStream::connect(addr)?;
+
+    // Cleanup all fixed files (if any), then accept into an allocated slot.
+    let _ = ring.submitter().unregister_files();
+    ring.submitter().register_files_sparse(1).unwrap();
+
+    let accept_e = opcode::Accept::new(fd, std::ptr::null_mut(), std::ptr::null_mut())
+        .file_index(Some(types::DestinationSlot::auto_target()));
+
+    unsafe {
+        ring.submission()
+            .push(&accept_e.build().user_data(0x01).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[0].result(), 0);
+
+    // Install the fixed socket twice, with and without `O_CLOEXEC`.
+    let install_e = opcode::FixedFdInstall::new(types::Fixed(0));
+    let install_no_cloexec_e = opcode::FixedFdInstall::new(types::Fixed(0)).cloexec(false);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&install_e.build().user_data(0x02).into())
+            .expect("queue is full");
+        queue
+            .push(&install_no_cloexec_e.build().user_data(0x03).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x02);
+    assert_eq!(cqes[1].user_data(), 0x03);
+    assert!(cqes[0].result() >= 0);
+    assert!(cqes[1].result() >= 0);
+
+    let mut stream = unsafe { TcpStream::from_raw_fd(cqes[0].result()) };
+    let stream_no_cloexec = unsafe { TcpStream::from_raw_fd(cqes[1].result()) };
+
+    let fd_flags = |stream: &TcpStream| unsafe { libc::fcntl(stream.as_raw_fd(), libc::F_GETFD) };
+    assert_eq!(fd_flags(&stream) & libc::FD_CLOEXEC, libc::FD_CLOEXEC);
+    assert_eq!(fd_flags(&stream_no_cloexec) & libc::FD_CLOEXEC, 0);
+
+    // The installed descriptor refers to the accepted socket.
+    let text = b"The quick brown fox jumps over the lazy dog.";
+    stream.write_all(text)?;
+
+    let mut output = vec![0; text.len()];
+    client.read_exact(&mut output)?;
+    assert_eq!(&output, text);
+
+    // The fixed file stays registered, and must still be closed by unregistering.
+    drop(stream);
+    drop(stream_no_cloexec);
+    ring.submitter().unregister_files().unwrap();
+
+    Ok(())
+}
+
 pub fn test_tcp_connect<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     ring: &mut IoUring<S, C>,
     test: &Test,
@@ -1160,6 +1386,181 @@ pub fn test_tcp_recv_multi<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     Ok(())
 }
 
+pub fn test_tcp_recv_send_bundle<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    use io_uring::buf_ring;
+    use std::io::{Read, Write};
+
+    require!(
+        test;
+        test.probe.is_supported(opcode::Send::CODE);
+        test.probe.is_supported(opcode::Recv::CODE);
+        ring.params().is_feature_recvsend_bundle();
+    );
+
+    println!("test tcp_recv_send_bundle");
+
+    let (mut send_stream, mut recv_stream) = tcp_pair()?;
+
+    let send_fd = types::Fd(send_stream.as_raw_fd());
+    let recv_fd = types::Fd(recv_stream.as_raw_fd());
+
+    // 4 buffers of 256 bytes to receive into, and 3 to send from.
+    let recv_ring = buf_ring::Builder::new(0xbeef)
+        .ring_entries(4)
+        .buf_len(256)
+        .build(&ring.submitter())?;
+    let send_ring = buf_ring::Builder::new(0xbeee)
+        .ring_entries(4)
+        .buf_cnt(3)
+        .buf_len(256)
+        .provide_all(false)
+        .build(&ring.submitter())?;
+
+    // A single bundled receive fills all 4 buffers.
+    let input: Vec<u8> = (0..1024).map(|i| i as u8).collect();
+    send_stream.write_all(&input)?;
+
+    let recv_e = opcode::Recv::new(recv_fd, std::ptr::null_mut(), 0)
+        .buf_group(recv_ring.bgid())
+        .bundle(true)
+        .build()
+        .flags(squeue::Flags::BUFFER_SELECT)
+        .user_data(0x01)
+        .into();
+
+    unsafe {
+        ring.submission().push(&recv_e).expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[0].result(), 1024);
+
+    let ids: Vec<u16> =
+        cqueue::bundle_buffer_ids(cqes[0].flags(), cqes[0].result(), 256, 4).collect();
+    assert_eq!(ids, [0, 1, 2, 3]);
+
+    let bufs = unsafe { recv_ring.get_bufs(&cqes[0]) };
+    assert_eq!(bufs.len(), 4);
+    for (i, buf) in bufs.iter().enumerate() {
+        assert_eq!(buf.bid(), i as u16);
+        assert_eq!(&buf[..], &input[i * 256..(i + 1) * 256]);
+    }
+
+    // Give the buffers back out of order, the next bundle then follows the ring order.
+    let mut bufs = bufs.into_iter();
+    let (b0, b1, b2, b3) = (bufs.next(), bufs.next(), bufs.next(), bufs.next());
+    drop((b2, b0, b3, b1));
+
+    // A multishot bundled receive fills 2 buffers with the next 512 bytes.
+    send_stream.write_all(&input[..512])?;
+
+    let recv_multi_e = opcode::RecvMulti::new(recv_fd, recv_ring.bgid())
+        .bundle(true)
+        .build()
+        .user_data(0x02)
+        .into();
+
+    unsafe {
+        ring.submission()
+            .push(&recv_multi_e)
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x02);
+    assert_eq!(cqes[0].result(), 512);
+    assert!(cqueue::more(cqes[0].flags()));
+
+    let bufs = unsafe { recv_ring.get_bufs(&cqes[0]) };
+    assert_eq!(bufs.len(), 2);
+    assert_eq!(bufs[0].bid(), 2);
+    assert_eq!(bufs[1].bid(), 0);
+    assert_eq!(&bufs[0][..], &input[..256]);
+    assert_eq!(&bufs[1][..], &input[256..512]);
+    drop(bufs);
+
+    // Cancel the multishot receive.
+    let cancel_e = opcode::AsyncCancel::new(0x02);
+
+    unsafe {
+        ring.submission()
+            .push(&cancel_e.build().user_data(0x03).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x02);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].user_data(), 0x03);
+    assert_eq!(cqes[1].result(), 0);
+
+    // Fill the send buffers with payloads of different lengths, and a single bundled send
+    // drains them all.
+    let lens = [100, 256, 30];
+    let mut offset = 0;
+    for &len in &lens {
+        let mut buf = send_ring.take().expect("no free buffer");
+        buf.set_len(len);
+        buf.copy_from_slice(&input[offset..offset + len]);
+        buf.provide();
+        offset += len;
+    }
+    assert!(send_ring.take().is_none());
+
+    let send_e = opcode::Send::new(send_fd, std::ptr::null(), 0)
+        .buf_group(send_ring.bgid())
+        .bundle(true)
+        .build()
+        .flags(squeue::Flags::BUFFER_SELECT)
+        .user_data(0x04)
+        .into();
+
+    unsafe {
+        ring.submission().push(&send_e).expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x04);
+    assert_eq!(cqes[0].result(), offset as i32);
+
+    let bufs = unsafe { send_ring.get_bufs(&cqes[0]) };
+    assert_eq!(bufs.len(), 3);
+    for (buf, &len) in bufs.iter().zip(&lens) {
+        assert_eq!(buf.len(), len);
+    }
+
+    let mut output = vec![0; offset];
+    recv_stream.read_exact(&mut output)?;
+    assert_eq!(output, &input[..offset]);
+
+    // The sent buffers can be taken again once dropped.
+    drop(bufs);
+    assert!(send_ring.take().is_some());
+
+    Ok(())
+}
+
 pub fn test_shutdown<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     ring: &mut IoUring<S, C>,
     test: &Test,
@@ -1216,6 +1617,10 @@ pub fn test_socket<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     test: &Test,
 ) -> anyhow::Result<()> {
     use socket2::{Domain, Protocol, Socket, Type};
+    use std::io::Write;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::net::UnixStream;
+    use std::ptr;
 
     require!(
         test;
@@ -1284,6 +1689,245 @@ pub fn test_socket<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     // If the fixed-socket operation worked properly, this must not fail.
     ring.submitter().unregister_files().unwrap();
 
+    // Bind and listen are available since 6.11, without them a fixed socket cannot be used as
+    // a server.
+    if !test.probe.is_supported(opcode::Bind::CODE)
+        || !test.probe.is_supported(opcode::Listen::CODE)
+    {
+        return Ok(());
+    }
+
+    // Build a listening server that only exists as a fixed file in slot 0, and accept a
+    // connection into slot 1.
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path().join("server.sock");
+    let mut server_addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+    server_addr.sun_family = libc::AF_UNIX as _;
+    for (dst, src) in server_addr
+        .sun_path
+        .iter_mut()
+        .zip(path.as_os_str().as_bytes())
+    {
+        *dst = *src as _;
+    }
+
+    ring.submitter().register_files_sparse(2).unwrap();
+
+    let server_slot = types::Fixed(0);
+    let socket_e = opcode::Socket::new(Domain::UNIX.into(), Type::STREAM.into(), 0)
+        .file_index(Some(
+            types::DestinationSlot::try_from_slot_target(0).unwrap(),
+        ))
+        .build()
+        .user_data(60)
+        .flags(squeue::Flags::IO_LINK)
+        .into();
+    let bind_e = opcode::Bind::new(
+        server_slot,
+        &server_addr as *const libc::sockaddr_un as *const _,
+        mem::size_of::<libc::sockaddr_un>() as _,
+    )
+    .build()
+    .user_data(61)
+    .flags(squeue::Flags::IO_LINK)
+    .into();
+    let listen_e = opcode::Listen::new(server_slot, 128)
+        .build()
+        .user_data(62)
+        .into();
+    unsafe {
+        let mut queue = ring.submission();
+        queue.push(&socket_e).expect("queue is full");
+        queue.push(&bind_e).expect("queue is full");
+        queue.push(&listen_e).expect("queue is full");
+    }
+    ring.submit_and_wait(3)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+    assert_eq!(cqes.len(), 3);
+    assert_eq!(cqes[0].user_data(), 60);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(cqes[1].user_data(), 61);
+    assert_eq!(cqes[1].result(), 0);
+    assert_eq!(cqes[2].user_data(), 62);
+    assert_eq!(cqes[2].result(), 0);
+
+    let mut client = UnixStream::connect(&path)?;
+    client.write_all(b"hello")?;
+
+    let accept_e = opcode::Accept::new(server_slot, ptr::null_mut(), ptr::null_mut())
+        .file_index(Some(
+            types::DestinationSlot::try_from_slot_target(1).unwrap(),
+        ))
+        .build()
+        .user_data(63)
+        .into();
+    unsafe {
+        ring.submission().push(&accept_e).expect("queue is full");
+    }
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 63);
+    assert_eq!(cqes[0].result(), 0);
+
+    let mut buf = [0u8; 5];
+    let recv_e = opcode::Recv::new(types::Fixed(1), buf.as_mut_ptr(), buf.len() as _)
+        .build()
+        .user_data(64)
+        .into();
+    unsafe {
+        ring.submission().push(&recv_e).expect("queue is full");
+    }
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 64);
+    assert_eq!(cqes[0].result(), 5);
+    assert_eq!(&buf, b"hello");
+
+    ring.submitter().unregister_files().unwrap();
+
+    Ok(())
+}
+
+pub fn test_socket_cmd<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    use std::io::Write;
+
+    // Socket commands were introduced in 6.7, like `FutexWait`.
+    // We cannot probe for the former, so we check for the latter as a proxy instead.
+    require!(
+        test;
+        test.probe.is_supported(opcode::UringCmd16::CODE);
+        test.probe.is_supported(opcode::FutexWait::CODE);
+    );
+
+    println!("test socket_cmd");
+
+    let (mut send_stream, recv_stream) = tcp_pair()?;
+
+    // Cleanup all fixed files (if any), then register both ends of the connection.
+    let _ = ring.submitter().unregister_files();
+    ring.submitter()
+        .register_files(&[send_stream.as_raw_fd(), recv_stream.as_raw_fd()])?;
+
+    let send_slot = types::Fixed(0);
+    let recv_slot = types::Fixed(1);
+
+    // Set TCP_NODELAY and SO_RCVBUF, and read SO_RCVBUF back.
+    let nodelay: libc::c_int = 1;
+    let rcvbuf: libc::c_int = 64 * 1024;
+    let mut rcvbuf_out: libc::c_int = 0;
+
+    let nodelay_e = opcode::SetSockOpt::new(
+        send_slot,
+        libc::IPPROTO_TCP as _,
+        libc::TCP_NODELAY as _,
+        &nodelay as *const libc::c_int as *const _,
+        mem::size_of::<libc::c_int>() as _,
+    );
+    let rcvbuf_e = opcode::SetSockOpt::new(
+        recv_slot,
+        libc::SOL_SOCKET as _,
+        libc::SO_RCVBUF as _,
+        &rcvbuf as *const libc::c_int as *const _,
+        mem::size_of::<libc::c_int>() as _,
+    );
+    let get_rcvbuf_e = opcode::GetSockOpt::new(
+        recv_slot,
+        libc::SOL_SOCKET as _,
+        libc::SO_RCVBUF as _,
+        &mut rcvbuf_out as *mut libc::c_int as *mut _,
+        mem::size_of::<libc::c_int>() as _,
+    );
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&nodelay_e.build().user_data(0x01).into())
+            .expect("queue is full");
+        queue
+            .push(
+                &rcvbuf_e
+                    .build()
+                    .user_data(0x02)
+                    .flags(squeue::Flags::IO_LINK)
+                    .into(),
+            )
+            .expect("queue is full");
+        queue
+            .push(&get_rcvbuf_e.build().user_data(0x03).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(3)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 3);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(cqes[1].user_data(), 0x02);
+    assert_eq!(cqes[1].result(), 0);
+    assert_eq!(cqes[2].user_data(), 0x03);
+    assert_eq!(cqes[2].result(), mem::size_of::<libc::c_int>() as i32);
+
+    assert!(send_stream.nodelay()?);
+    // The kernel doubles the requested value to account for bookkeeping overhead.
+    assert_eq!(rcvbuf_out, rcvbuf * 2);
+
+    // Queue some data, and check how much is waiting on the receiving end.
+    let text = b"hello";
+    send_stream.write_all(text)?;
+
+    let inq_e = opcode::SiocInq::new(recv_slot)
+        .build()
+        .user_data(0x04)
+        .into();
+    let outq_e = opcode::SiocOutq::new(send_slot)
+        .build()
+        .user_data(0x05)
+        .into();
+
+    // The data may take a moment to go through the loopback device.
+    let mut inq = 0;
+    for _ in 0..10 {
+        unsafe {
+            ring.submission().push(&inq_e).expect("queue is full");
+        }
+        ring.submit_and_wait(1)?;
+
+        let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+        assert_eq!(cqes.len(), 1);
+        assert_eq!(cqes[0].user_data(), 0x04);
+        inq = cqes[0].result();
+
+        if inq == text.len() as i32 {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(inq, text.len() as i32);
+
+    unsafe {
+        ring.submission().push(&outq_e).expect("queue is full");
+    }
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x05);
+    assert!(cqes[0].result() >= 0);
+
+    ring.submitter().unregister_files()?;
+
     Ok(())
 }
 
@@ -1425,3 +2069,74 @@ pub fn test_udp_recvmsg_multishot<S: squeue::EntryMarker, C: cqueue::EntryMarker
 
     Ok(())
 }
+
+pub fn test_register_napi<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    // NAPI registration was introduced in kernel 6.9, Ftruncate is the closest proxy.
+    require!(
+        test;
+        test.probe.is_supported(opcode::Ftruncate::CODE);
+    );
+
+    println!("test register_napi");
+
+    let napi = types::Napi::new()
+        .busy_poll_timeout(100)
+        .prefer_busy_poll(true);
+
+    let prev = match ring.submitter().register_napi(&napi) {
+        Ok(prev) => prev,
+        Err(ref err) if err.raw_os_error() == Some(libc::EOPNOTSUPP) => {
+            println!("skipping register_napi: kernel built without busy poll support");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Nothing was registered before.
+    assert_eq!(prev.get_busy_poll_timeout(), 0);
+    assert!(!prev.get_prefer_busy_poll());
+
+    // Registering again replaces the settings, and returns the ones in effect.
+    let prev = ring
+        .submitter()
+        .register_napi(&types::Napi::new().busy_poll_timeout(50))?;
+    assert_eq!(prev.get_busy_poll_timeout(), 100);
+    assert!(prev.get_prefer_busy_poll());
+
+    // The ring still works on loopback while busy polling.
+    let (send_stream, recv_stream) = tcp_pair()?;
+    let buf = [0x5a; 32];
+    let mut out = [0; 32];
+
+    let send_e = opcode::Send::new(types::Fd(send_stream.as_raw_fd()), buf.as_ptr(), 32);
+    let recv_e = opcode::Recv::new(types::Fd(recv_stream.as_raw_fd()), out.as_mut_ptr(), 32);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&send_e.build().user_data(0x01).into())
+            .expect("queue is full");
+        queue
+            .push(&recv_e.build().user_data(0x02).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].result(), 32);
+    assert_eq!(cqes[1].result(), 32);
+    assert_eq!(out, buf);
+
+    let prev = ring.submitter().unregister_napi()?;
+    assert_eq!(prev.get_busy_poll_timeout(), 50);
+    assert!(!prev.get_prefer_busy_poll());
+
+    Ok(())
+}
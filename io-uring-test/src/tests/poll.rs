@@ -235,3 +235,201 @@ pub fn test_eventfd_poll_multi<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
 
     Ok(())
 }
+
+pub fn test_eventfd_poll_update<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::PollAdd::CODE);
+        test.probe.is_supported(opcode::MkDirAt::CODE); // Available since 5.15 when poll update was available 5.13.
+    );
+
+    println!("test eventfd_poll_update");
+
+    let fd = unsafe {
+        let fd = libc::eventfd(0, libc::EFD_CLOEXEC);
+
+        if fd == -1 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        File::from_raw_fd(fd)
+    };
+
+    // add poll
+
+    let poll_e = opcode::PollAdd::new(types::Fd(fd.as_raw_fd()), libc::POLLIN as _);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&poll_e.build().user_data(0x09).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(ring.completion().len(), 0);
+
+    // update user data only, the poll keeps waiting
+
+    let update_e = opcode::PollUpdate::new(0x09).new_user_data(Some(0x0a));
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&update_e.build().user_data(0x0b).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+    thread::sleep(Duration::from_millis(200));
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x0b);
+    assert_eq!(cqes[0].result(), 0);
+
+    // update the event mask, an eventfd is always writable
+
+    let update_e = opcode::PollUpdate::new(0x0a).flags(Some(libc::POLLOUT as _));
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&update_e.build().user_data(0x0c).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x0a);
+    assert_eq!(cqes[1].user_data(), 0x0c);
+    assert_eq!(cqes[0].result(), libc::POLLOUT as _);
+    assert_eq!(cqes[1].result(), 0);
+
+    // the poll is gone, updating it again fails
+
+    let update_e = opcode::PollUpdate::new(0x0a).new_user_data(Some(0x0d));
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&update_e.build().user_data(0x0d).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x0d);
+    assert_eq!(cqes[0].result(), -libc::ENOENT);
+
+    Ok(())
+}
+
+pub fn test_eventfd_poll_level<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::PollAdd::CODE);
+        test.probe.is_supported(opcode::SendZc::CODE); // Available since 6.0, as is level-triggered poll.
+    );
+
+    println!("test eventfd_poll_level");
+
+    let mut fd = unsafe {
+        let fd = libc::eventfd(0, libc::EFD_CLOEXEC);
+
+        if fd == -1 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        File::from_raw_fd(fd)
+    };
+
+    // the eventfd is readable when the poll is armed, and is never read
+
+    fd.write_all(&0x1u64.to_ne_bytes())?;
+
+    let poll_e = opcode::PollAdd::new(types::Fd(fd.as_raw_fd()), libc::POLLIN as _)
+        .multi(true)
+        .level(true);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&poll_e.build().user_data(0x0e).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+    thread::sleep(Duration::from_millis(100));
+    ring.submit()?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert!(!cqes.is_empty());
+    assert_eq!(cqes[0].user_data(), 0x0e);
+
+    // Kernels since 6.2 reject level-triggered poll requests outright.
+    if cqes[0].result() == -libc::EINVAL {
+        assert_eq!(cqes.len(), 1);
+        println!("skipping: level-triggered poll not accepted by this kernel");
+        return Ok(());
+    }
+
+    // an edge-triggered poll would only fire once, when armed
+
+    assert!(cqes.len() > 1);
+    for cqe in &cqes {
+        assert_eq!(cqe.user_data(), 0x0e);
+        assert_eq!(cqe.result(), libc::POLLIN as _);
+        assert!(cqueue::more(cqe.flags()));
+    }
+
+    // remove the poll, so that it does not fire for later tests
+
+    let remove_e = opcode::PollRemove::new(0x0e);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&remove_e.build().user_data(0x0f).into())
+            .expect("queue is full");
+    }
+
+    let mut removed = false;
+    let mut terminated = false;
+
+    while !(removed && terminated) {
+        ring.submit_and_wait(1)?;
+
+        let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+        for cqe in cqes {
+            match cqe.user_data() {
+                // The poll may have stopped on its own if the completion queue overflowed.
+                0x0f => {
+                    assert!(cqe.result() == 0 || cqe.result() == -libc::ENOENT);
+                    removed = true;
+                }
+                0x0e => terminated |= !cqueue::more(cqe.flags()),
+                user_data => panic!("unexpected completion {:#x}", user_data),
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::Test;
+use io_uring::{cqueue, opcode, squeue, IoUring};
+use std::mem;
+use std::process::Command;
+
+pub fn test_waitid<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::WaitId::CODE);
+    );
+
+    println!("test waitid");
+
+    let child = Command::new("/bin/true").spawn()?;
+
+    let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+    let waitid_e = opcode::WaitId::new(libc::P_PID, child.id(), libc::WEXITED, &mut info);
+
+    unsafe {
+        ring.submission()
+            .push(&waitid_e.build().user_data(0x01).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x01);
+    assert_eq!(cqes[0].result(), 0);
+
+    assert_eq!(info.si_signo, libc::SIGCHLD);
+    assert_eq!(info.si_code, libc::CLD_EXITED);
+    assert_eq!(unsafe { info.si_pid() }, child.id() as libc::pid_t);
+    assert_eq!(unsafe { info.si_status() }, 0);
+
+    Ok(())
+}
@@ -1,6 +1,35 @@
 use crate::Test;
 use io_uring::{cqueue, opcode, squeue, types, IoUring};
 
+// Submit `count` NOPs with consecutive user data starting at `first`, and check that they all
+// complete.
+fn run_nops<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    first: u64,
+    count: u64,
+) -> anyhow::Result<()> {
+    unsafe {
+        let mut queue = ring.submission();
+        for i in first..first + count {
+            let nop_e = opcode::Nop::new().build().user_data(i).into();
+            queue.push(&nop_e).expect("queue is full");
+        }
+    }
+
+    ring.submit_and_wait(count as usize)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), count as usize);
+    for (i, cqe) in cqes.iter().enumerate() {
+        assert_eq!(cqe.user_data(), first + i as u64);
+        assert_eq!(cqe.result(), 0);
+    }
+
+    Ok(())
+}
+
 pub fn test_nop<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     ring: &mut IoUring<S, C>,
     test: &Test,
@@ -305,3 +334,187 @@ pub fn test_msg_ring_send_fd<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
 
     Ok(())
 }
+
+pub fn test_resize<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    _ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    // Resizing was introduced in kernel 6.13, which has no new opcode to probe. Require Bind
+    // from 6.11, and skip if the kernel rejects the resize.
+    require!(
+        test;
+        test.probe.is_supported(opcode::Bind::CODE);
+    );
+
+    println!("test resize");
+
+    // Resizing is only supported for rings that defer task work.
+    let mut ring = IoUring::<S, C>::generic_builder()
+        .setup_single_issuer()
+        .setup_defer_taskrun()
+        .build(8)?;
+
+    // Queue two entries before resizing, they are carried over to the new queue.
+    unsafe {
+        let mut queue = ring.submission();
+        for i in 0..2 {
+            let nop_e = opcode::Nop::new().build().user_data(i).into();
+            queue.push(&nop_e).expect("queue is full");
+        }
+    }
+
+    // Resizing checks where it expects the SQ array against the offset reported by the kernel
+    // at setup, and fails if they differ.
+    match ring.resize(64, 128) {
+        Ok(()) => (),
+        Err(ref err) if err.raw_os_error() == Some(libc::EINVAL) => {
+            println!("skipping resize: not supported by the kernel");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    assert_eq!(ring.params().sq_entries(), 64);
+    assert_eq!(ring.params().cq_entries(), 128);
+    assert_eq!(ring.submission().capacity(), 64);
+    assert_eq!(ring.submission().len(), 2);
+    assert_eq!(ring.completion().capacity(), 128);
+
+    // Fill the larger queue.
+    unsafe {
+        let mut queue = ring.submission();
+        for i in 2..64 {
+            let nop_e = opcode::Nop::new().build().user_data(i).into();
+            queue.push(&nop_e).expect("queue is full");
+        }
+        assert!(queue.is_full());
+    }
+
+    ring.submit_and_wait(64)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 64);
+    for (i, cqe) in cqes.iter().enumerate() {
+        assert_eq!(cqe.user_data(), i as u64);
+        assert_eq!(cqe.result(), 0);
+    }
+
+    // Shrink the queues again, with a completion left in the completion queue.
+    unsafe {
+        let nop_e = opcode::Nop::new().build().user_data(0x42).into();
+        ring.submission().push(&nop_e).expect("queue is full");
+    }
+    ring.submit_and_wait(1)?;
+
+    ring.resize(4, 8)?;
+
+    assert_eq!(ring.params().sq_entries(), 4);
+    assert_eq!(ring.params().cq_entries(), 8);
+    assert_eq!(ring.submission().capacity(), 4);
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x42);
+
+    Ok(())
+}
+
+pub fn test_setup_no_mmap<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    _ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    use std::alloc::{self, Layout};
+
+    require!(
+        test;
+    );
+
+    println!("test setup_no_mmap");
+
+    // Memory allocated by the crate, for queues that fit in a page and for larger ones.
+    for &entries in &[8, 1024] {
+        let mut ring = IoUring::<S, C>::generic_builder()
+            .setup_no_mmap()
+            .build(entries)?;
+
+        if !ring.params().is_setup_no_mmap() {
+            println!("skipping setup_no_mmap: not supported by the kernel");
+            return Ok(());
+        }
+
+        assert_eq!(ring.params().sq_entries(), entries);
+        run_nops(&mut ring, 0, entries as u64)?;
+    }
+
+    // Memory provided by the application.
+    let mut builder = IoUring::<S, C>::generic_builder();
+    let (sqes_len, rings_len) = builder.no_mmap_memory_len(8);
+    let sqes_layout = Layout::from_size_align(sqes_len, 4096)?;
+    let rings_layout = Layout::from_size_align(rings_len, 4096)?;
+
+    unsafe {
+        let sqes = alloc::alloc_zeroed(sqes_layout);
+        let rings = alloc::alloc_zeroed(rings_layout);
+        assert!(!sqes.is_null() && !rings.is_null());
+
+        let res = builder
+            .setup_no_mmap_with_memory(sqes as _, sqes_len, rings as _, rings_len)
+            .build(8)
+            .map_err(anyhow::Error::from)
+            .and_then(|mut ring| {
+                assert!(ring.params().is_setup_no_mmap());
+                run_nops(&mut ring, 0, 8)
+            });
+
+        // Memory that is too small is rejected.
+        let too_small = builder
+            .setup_no_mmap_with_memory(sqes as _, sqes_len, rings as _, rings_len - 1)
+            .build(8);
+
+        alloc::dealloc(sqes, sqes_layout);
+        alloc::dealloc(rings, rings_layout);
+
+        res?;
+        assert_eq!(
+            too_small.err().map(|err| err.kind()),
+            Some(std::io::ErrorKind::InvalidInput)
+        );
+    }
+
+    Ok(())
+}
+
+pub fn test_setup_no_sqarray<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    _ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+    );
+
+    println!("test setup_no_sqarray");
+
+    let mut ring = match IoUring::<S, C>::generic_builder()
+        .setup_no_sqarray()
+        .build(8)
+    {
+        Ok(ring) => ring,
+        Err(ref err) if err.raw_os_error() == Some(libc::EINVAL) => {
+            println!("skipping setup_no_sqarray: not supported by the kernel");
+            return Ok(());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    assert!(ring.params().is_setup_no_sqarray());
+
+    // Go around the queue a few times, so that positions wrap.
+    for round in 0..4 {
+        run_nops(&mut ring, round * 6, 6)?;
+    }
+
+    Ok(())
+}
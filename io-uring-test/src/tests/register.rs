@@ -1,5 +1,7 @@
 use crate::Test;
 use io_uring::{cqueue, opcode, squeue, IoUring};
+use std::fs::File;
+use std::os::unix::io::{AsRawFd, FromRawFd};
 
 pub fn test_register_files_sparse<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
     ring: &mut IoUring<S, C>,
@@ -68,3 +70,234 @@ pub fn test_register_files_sparse<S: squeue::EntryMarker, C: cqueue::EntryMarker
 
     Ok(())
 }
+
+pub fn test_register_files_tags<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        ring.params().is_feature_resource_tagging();
+    );
+
+    println!("test register_files_tags");
+
+    const READ_TAG: u64 = 0xbadcafe;
+    const WRITE_TAG: u64 = 0xbadf00d;
+
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    let read = unsafe { File::from_raw_fd(fds[0]) };
+    let write = unsafe { File::from_raw_fd(fds[1]) };
+
+    // Cleanup all fixed files (if any).
+    let _ = ring.submitter().unregister_files();
+
+    // A sparse entry cannot be tagged.
+
+    if ring
+        .submitter()
+        .register_files_tags(&[read.as_raw_fd(), -1], &[READ_TAG, WRITE_TAG])
+        .is_ok()
+    {
+        return Err(anyhow::anyhow!(
+            "register_files_tags should not accept a tag on a sparse entry"
+        ));
+    }
+
+    ring.submitter()
+        .register_files_tags(&[read.as_raw_fd(), -1], &[READ_TAG, 0])?;
+
+    // Fill in the sparse entry.
+
+    let updated =
+        ring.submitter()
+            .register_files_update_tag(1, &[write.as_raw_fd()], &[WRITE_TAG])?;
+    assert_eq!(updated, 1);
+
+    // Both files are released by unregistering the table, each posting a CQE with its tag.
+
+    ring.submitter().unregister_files()?;
+    ring.submit_and_wait(2)?;
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), READ_TAG);
+    assert_eq!(cqes[1].user_data(), WRITE_TAG);
+    assert_eq!(cqes[0].result(), 0);
+    assert_eq!(cqes[1].result(), 0);
+
+    Ok(())
+}
+
+pub fn test_register_ring_fd<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    // Registered ring fds were introduced in kernel 5.18, as was the opcode for MsgRingData.
+    require!(
+        test;
+        test.probe.is_supported(opcode::MsgRingData::CODE);
+    );
+
+    println!("test register_ring_fd");
+
+    ring.submitter().register_ring_fd()?;
+
+    // See that the ring fd cannot be registered twice.
+
+    if ring.submitter().register_ring_fd().is_ok() {
+        return Err(anyhow::anyhow!(
+            "register_ring_fd should not have succeeded twice in a row"
+        ));
+    }
+
+    // See that the ring can be entered through the registered index.
+
+    unsafe {
+        ring.submission()
+            .push(&opcode::Nop::new().build().user_data(0x42).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x42);
+    assert_eq!(cqes[0].result(), 0);
+
+    // See that other threads keep entering the ring through its fd, and cannot unregister it.
+
+    unsafe {
+        ring.submission()
+            .push(&opcode::Nop::new().build().user_data(0x44).into())
+            .expect("queue is full");
+    }
+
+    let shared = &*ring;
+    std::thread::scope(|s| {
+        s.spawn(|| -> anyhow::Result<()> {
+            shared.submit_and_wait(1)?;
+
+            if shared.submitter().unregister_ring_fd().is_ok() {
+                return Err(anyhow::anyhow!(
+                    "unregister_ring_fd should not have succeeded on another thread"
+                ));
+            }
+
+            Ok(())
+        })
+        .join()
+        .unwrap()
+    })?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x44);
+    assert_eq!(cqes[0].result(), 0);
+
+    ring.submitter().unregister_ring_fd()?;
+
+    if ring.submitter().unregister_ring_fd().is_ok() {
+        return Err(anyhow::anyhow!(
+            "unregister_ring_fd should not have succeeded twice in a row"
+        ));
+    }
+
+    // And that the ring fd is used again afterwards.
+
+    unsafe {
+        ring.submission()
+            .push(&opcode::Nop::new().build().user_data(0x43).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x43);
+
+    // See that a registration left by a thread that exited does not prevent registering again.
+
+    let shared = &*ring;
+    std::thread::scope(|s| {
+        s.spawn(|| shared.submitter().register_ring_fd())
+            .join()
+            .unwrap()
+    })?;
+
+    ring.submitter().register_ring_fd()?;
+
+    unsafe {
+        ring.submission()
+            .push(&opcode::Nop::new().build().user_data(0x45).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x45);
+
+    ring.submitter().unregister_ring_fd()?;
+
+    Ok(())
+}
+
+pub fn test_register_iowq_aff<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    // io-wq affinity was introduced in kernel 5.14, MkDirAt in 5.15 is the closest proxy.
+    require!(
+        test;
+        test.probe.is_supported(opcode::MkDirAt::CODE);
+    );
+
+    println!("test register_iowq_aff");
+
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    let ret = unsafe { libc::sched_getaffinity(0, std::mem::size_of_val(&cpu_set), &mut cpu_set) };
+    if ret != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    // See that the affinity can be set to the current one, and removed again.
+
+    ring.submitter().register_iowq_aff(&cpu_set)?;
+    ring.submitter().unregister_iowq_aff()?;
+
+    // Same with a list of CPU ids.
+
+    let cpu = (0..std::mem::size_of_val(&cpu_set) * 8)
+        .find(|&cpu| unsafe { libc::CPU_ISSET(cpu, &cpu_set) })
+        .expect("no cpu in the affinity mask");
+
+    ring.submitter().register_iowq_aff_cpus(&[cpu])?;
+    ring.submitter().unregister_iowq_aff()?;
+
+    // See that a CPU id that does not fit in a cpu_set_t is rejected.
+
+    match ring.submitter().register_iowq_aff_cpus(&[1 << 20]) {
+        Err(ref err) if err.kind() == std::io::ErrorKind::InvalidInput => (),
+        res => {
+            return Err(anyhow::anyhow!(
+                "register_iowq_aff_cpus should have failed: {:?}",
+                res
+            ))
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use crate::Test;
 use io_uring::{cqueue, opcode, squeue, types, IoUring};
+use std::fs::File;
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd};
 use std::time::Instant;
 
 pub fn test_timeout<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
@@ -325,3 +328,155 @@ pub fn test_timeout_submit_args<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
 
     Ok(())
 }
+
+pub fn test_timeout_update<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::Timeout::CODE);
+        test.probe.is_supported(opcode::Shutdown::CODE); // Available since 5.11, as is timeout update.
+    );
+
+    println!("test timeout_update");
+
+    // add timeout
+
+    let ts = types::Timespec::new().sec(10);
+    let timeout_e = opcode::Timeout::new(&ts);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&timeout_e.build().user_data(0x1d).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    // shorten timeout
+
+    let ts = types::Timespec::new().sec(1);
+    let update_e = opcode::TimeoutUpdate::new(0x1d, &ts);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&update_e.build().user_data(0x1e).into())
+            .expect("queue is full");
+    }
+
+    let start = Instant::now();
+    ring.submit_and_wait(2)?;
+
+    assert_eq!(start.elapsed().as_secs(), 1);
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 2);
+    assert_eq!(cqes[0].user_data(), 0x1d);
+    assert_eq!(cqes[1].user_data(), 0x1e);
+    assert_eq!(cqes[0].result(), -libc::ETIME);
+    assert_eq!(cqes[1].result(), 0);
+
+    // update a timeout that is gone
+
+    let update_e = opcode::TimeoutUpdate::new(0x1d, &ts);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&update_e.build().user_data(0x1f).into())
+            .expect("queue is full");
+    }
+
+    ring.submit_and_wait(1)?;
+
+    let cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+
+    assert_eq!(cqes.len(), 1);
+    assert_eq!(cqes[0].user_data(), 0x1f);
+    assert_eq!(cqes[0].result(), -libc::ENOENT);
+
+    Ok(())
+}
+
+pub fn test_link_timeout_update<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    ring: &mut IoUring<S, C>,
+    test: &Test,
+) -> anyhow::Result<()> {
+    require!(
+        test;
+        test.probe.is_supported(opcode::PollAdd::CODE);
+        test.probe.is_supported(opcode::LinkTimeout::CODE);
+        test.probe.is_supported(opcode::MkDirAt::CODE); // Available since 5.15, as is link timeout update.
+    );
+
+    println!("test link_timeout_update");
+
+    let fd = unsafe {
+        let fd = libc::eventfd(0, libc::EFD_CLOEXEC);
+
+        if fd == -1 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        File::from_raw_fd(fd)
+    };
+
+    // add a poll that never completes, linked to a timeout
+
+    let poll_e = opcode::PollAdd::new(types::Fd(fd.as_raw_fd()), libc::POLLIN as _);
+    let ts = types::Timespec::new().sec(10);
+    let timeout_e = opcode::LinkTimeout::new(&ts);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(
+                &poll_e
+                    .build()
+                    .flags(squeue::Flags::IO_LINK)
+                    .user_data(0x20)
+                    .into(),
+            )
+            .expect("queue is full");
+        queue
+            .push(&timeout_e.build().user_data(0x21).into())
+            .expect("queue is full");
+    }
+
+    ring.submit()?;
+
+    // shorten timeout
+
+    let ts = types::Timespec::new().sec(1);
+    let update_e = opcode::LinkTimeoutUpdate::new(0x21, &ts);
+
+    unsafe {
+        let mut queue = ring.submission();
+        queue
+            .push(&update_e.build().user_data(0x22).into())
+            .expect("queue is full");
+    }
+
+    let start = Instant::now();
+    ring.submit_and_wait(3)?;
+
+    assert_eq!(start.elapsed().as_secs(), 1);
+
+    let mut cqes: Vec<cqueue::Entry> = ring.completion().map(Into::into).collect();
+    cqes.sort_by_key(|cqe| cqe.user_data());
+
+    assert_eq!(cqes.len(), 3);
+    assert_eq!(cqes[0].user_data(), 0x20);
+    assert_eq!(cqes[1].user_data(), 0x21);
+    assert_eq!(cqes[2].user_data(), 0x22);
+    assert_eq!(cqes[0].result(), -libc::ECANCELED);
+    assert_eq!(cqes[1].result(), -libc::ETIME);
+    assert_eq!(cqes[2].result(), 0);
+
+    Ok(())
+}
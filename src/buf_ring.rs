@@ -0,0 +1,488 @@
+//! Provided buffer rings.
+//!
+//! A buffer ring hands a group of buffers to the kernel, which picks one of them for each request
+//! that sets [`Flags::BUFFER_SELECT`](crate::squeue::Flags::BUFFER_SELECT) with the ring's buffer
+//! group id, and reports the chosen buffer id in the completion. [`BufRing`] owns the ring and its
+//! buffers, and gives the buffers back to the kernel once the application is done with them.
+//!
+//! Sends can also take their data from a buffer ring. Such a ring is built without handing its
+//! buffers to the kernel, see [`Builder::provide_all`], and the application fills buffers taken
+//! with [`BufRing::take`] before handing them over with [`Buf::provide`].
+
+use std::cell::{Cell, RefCell};
+use std::mem::{size_of, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::os::unix::io::AsRawFd;
+use std::rc::Rc;
+use std::sync::atomic::{self, AtomicU16};
+use std::{fmt, io, ptr, slice};
+
+use crate::register::execute;
+use crate::types::{BufRingEntry, BufRingFlags};
+use crate::util::{cast_ptr, Mmap, OwnedFd};
+use crate::{cqueue, sys, Submitter};
+
+/// Builder for a [`BufRing`].
+#[derive(Debug, Clone, Copy)]
+pub struct Builder {
+    bgid: u16,
+    ring_entries: u16,
+    buf_cnt: u16,
+    buf_len: usize,
+    kernel_alloc: bool,
+    provide_all: bool,
+}
+
+impl Builder {
+    /// Create a new builder for a buffer ring with the buffer group id `bgid`.
+    ///
+    /// The caller is responsible for picking a `bgid` that does not conflict with other buffer
+    /// groups registered with the same io_uring instance.
+    pub fn new(bgid: u16) -> Builder {
+        Builder {
+            bgid,
+            ring_entries: 128,
+            buf_cnt: 0,
+            buf_len: 4096,
+            kernel_alloc: false,
+            provide_all: true,
+        }
+    }
+
+    /// The number of entries of the ring, rounded up to a power of two. It is raised to the number
+    /// of buffers if that is larger. The kernel enforces a maximum of 32768.
+    ///
+    /// Defaults to 128.
+    pub fn ring_entries(&mut self, ring_entries: u16) -> &mut Self {
+        self.ring_entries = ring_entries;
+        self
+    }
+
+    /// The number of buffers to allocate. If left to zero, one buffer is allocated per ring entry.
+    pub fn buf_cnt(&mut self, buf_cnt: u16) -> &mut Self {
+        self.buf_cnt = buf_cnt;
+        self
+    }
+
+    /// The length of each buffer in bytes.
+    ///
+    /// Defaults to 4096.
+    pub fn buf_len(&mut self, buf_len: usize) -> &mut Self {
+        self.buf_len = buf_len;
+        self
+    }
+
+    /// Let the kernel allocate the ring, which is then mapped from the io_uring file descriptor.
+    /// This avoids placing the ring at an address picked by the application, which is useful
+    /// when the address space layout is restricted. The buffers are still allocated by the
+    /// application.
+    ///
+    /// Available since 6.4.
+    pub fn kernel_alloc(&mut self, kernel_alloc: bool) -> &mut Self {
+        self.kernel_alloc = kernel_alloc;
+        self
+    }
+
+    /// Whether all buffers are handed to the kernel when the ring is built, and given back to it
+    /// when dropped, which is what receives need. Rings that send data from their buffers disable
+    /// this: their buffers stay with the application until filled and handed over with
+    /// [`Buf::provide`], and go back to [`BufRing::take`] when dropped.
+    ///
+    /// Defaults to true.
+    pub fn provide_all(&mut self, provide_all: bool) -> &mut Self {
+        self.provide_all = provide_all;
+        self
+    }
+
+    /// Allocate the ring and its buffers, and register the ring with the io_uring instance of
+    /// `submitter`. All buffers are made available to the kernel, unless
+    /// [`provide_all`](Self::provide_all) is disabled.
+    ///
+    /// Available since 5.19.
+    pub fn build(&self, submitter: &Submitter<'_>) -> io::Result<BufRing> {
+        let mut b = *self;
+
+        if b.buf_cnt == 0 || b.ring_entries < b.buf_cnt {
+            let max = b.ring_entries.max(b.buf_cnt);
+            b.buf_cnt = max;
+            b.ring_entries = max;
+        }
+
+        // 2^16 does not fit in a u16, and the kernel does not accept more than 2^15 entries.
+        if b.ring_entries == 0 || b.ring_entries > (1 << 15) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ring_entries must be between 1 and 32768",
+            ));
+        }
+        b.ring_entries = b.ring_entries.next_power_of_two();
+
+        if b.buf_len == 0 || b.buf_len > u32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "buf_len must be between 1 and u32::MAX",
+            ));
+        }
+
+        let ring_len = size_of::<BufRingEntry>() * b.ring_entries as usize;
+        let bufs_len = b.buf_len.checked_mul(b.buf_cnt as usize).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "buffers size overflows usize")
+        })?;
+
+        let bufs = Mmap::new_anonymous(bufs_len)?;
+        let fd = submitter.fd().try_clone()?;
+
+        let ring = if b.kernel_alloc {
+            submitter.register_buf_ring_with_flags(
+                0,
+                b.ring_entries,
+                b.bgid,
+                BufRingFlags::MMAP,
+            )?;
+
+            let offset =
+                sys::IORING_OFF_PBUF_RING as u64 | (b.bgid as u64) << sys::IORING_OFF_PBUF_SHIFT;
+            match Mmap::new(&fd, offset as _, ring_len) {
+                Ok(ring) => ring,
+                Err(err) => {
+                    let _ = submitter.unregister_buf_ring(b.bgid);
+                    return Err(err);
+                }
+            }
+        } else {
+            // The ring must be page-aligned and zero-filled, which an anonymous mapping
+            // guarantees.
+            let ring = Mmap::new_anonymous(ring_len)?;
+            submitter.register_buf_ring(ring.as_mut_ptr() as _, b.ring_entries, b.bgid)?;
+            ring
+        };
+
+        let inner = Inner {
+            fd,
+            bgid: b.bgid,
+            mask: b.ring_entries - 1,
+            buf_cnt: b.buf_cnt,
+            buf_len: b.buf_len,
+            ring,
+            bufs,
+            local_tail: Cell::new(0),
+            provide_all: b.provide_all,
+            lens: (0..b.buf_cnt).map(|_| Cell::new(0)).collect(),
+            next: (0..b.buf_cnt).map(|_| Cell::new(0)).collect(),
+            last: Cell::new(None),
+            free: RefCell::new(Vec::new()),
+        };
+
+        if inner.provide_all {
+            for bid in 0..inner.buf_cnt {
+                inner.push(bid, inner.buf_len as _);
+            }
+            inner.sync();
+        } else {
+            inner.free.borrow_mut().extend((0..inner.buf_cnt).rev());
+        }
+
+        Ok(BufRing {
+            inner: Rc::new(inner),
+        })
+    }
+}
+
+struct Inner {
+    // A duplicate of the ring fd, used to unregister the buffer ring when it is dropped.
+    fd: OwnedFd,
+
+    bgid: u16,
+
+    // One less than the number of ring entries, which is a power of two.
+    mask: u16,
+
+    buf_cnt: u16,
+    buf_len: usize,
+
+    // The ring of entries describing the buffers made available to the kernel, either allocated
+    // by the application or mapped from the ring fd when the kernel allocated it.
+    ring: Mmap,
+
+    // The buffers, `buf_cnt` of `buf_len` bytes each.
+    bufs: Mmap,
+
+    // The tail of the ring as seen by the application. The tail value is allowed to exceed the
+    // number of entries so that the kernel can tell a full ring from an empty one; the mask is only
+    // applied when indexing the ring.
+    local_tail: Cell<u16>,
+
+    // Whether buffers go back to the ring when dropped, or to `free`.
+    provide_all: bool,
+
+    // The length each buffer was last added to the ring with.
+    lens: Box<[Cell<u32>]>,
+
+    // The buffer added to the ring right after each buffer, and the last buffer added. The kernel
+    // consumes the ring in order, so this is the order the buffers of a bundle follow each other.
+    // The entry of a buffer is only overwritten once it is added back to the ring, which cannot
+    // happen while the application still has to take it with `get_bufs`.
+    next: Box<[Cell<u16>]>,
+    last: Cell<Option<u16>>,
+
+    // The buffers held by neither the kernel nor the application, with `provide_all` disabled.
+    free: RefCell<Vec<u16>>,
+}
+
+impl Inner {
+    fn buf_ptr(&self, bid: u16) -> *mut u8 {
+        unsafe { (self.bufs.as_mut_ptr() as *mut u8).add(bid as usize * self.buf_len) }
+    }
+
+    // Add the first `len` bytes of the `bid` buffer to the ring. It is not visible to the kernel
+    // until `sync` is called.
+    fn push(&self, bid: u16, len: u32) {
+        let tail = self.local_tail.get();
+        self.local_tail.set(tail.wrapping_add(1));
+
+        let entries = self.ring.as_mut_ptr() as *mut BufRingEntry;
+        let entry = unsafe { &mut *entries.add((tail & self.mask) as usize) };
+
+        entry.set_addr(self.buf_ptr(bid) as _);
+        entry.set_len(len);
+        entry.set_bid(bid);
+
+        self.lens[bid as usize].set(len);
+        if let Some(last) = self.last.replace(Some(bid)) {
+            self.next[last as usize].set(bid);
+        }
+    }
+
+    // Make the buffers pushed so far visible to the kernel.
+    fn sync(&self) {
+        unsafe {
+            let tail = BufRingEntry::tail(self.ring.as_mut_ptr() as *const BufRingEntry);
+            (*(tail as *const AtomicU16)).store(self.local_tail.get(), atomic::Ordering::Release);
+        }
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        let arg = sys::io_uring_buf_reg {
+            bgid: self.bgid,
+            ..Default::default()
+        };
+        let arg = cast_ptr::<sys::io_uring_buf_reg>(&arg);
+        let _ = execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_UNREGISTER_PBUF_RING,
+            arg as *const _,
+            1,
+        );
+    }
+}
+
+/// A ring of provided buffers registered with an io_uring instance, see the
+/// [module documentation](self).
+///
+/// The ring is unregistered once it and all buffers taken from it are dropped. This must not
+/// happen while requests that may select a buffer from its group are still in flight.
+#[derive(Clone)]
+pub struct BufRing {
+    inner: Rc<Inner>,
+}
+
+impl BufRing {
+    /// The buffer group id of the ring, to be passed to the `buf_group` of requests.
+    pub fn bgid(&self) -> u16 {
+        self.inner.bgid
+    }
+
+    /// The number of buffers of the ring.
+    pub fn buf_cnt(&self) -> u16 {
+        self.inner.buf_cnt
+    }
+
+    /// The length of each buffer in bytes.
+    pub fn buf_len(&self) -> usize {
+        self.inner.buf_len
+    }
+
+    /// Take the buffer selected by the kernel for the completion `cqe`, as reported by
+    /// [`cqueue::buffer_select`]. The buffer holds the `result` bytes of the completion and is
+    /// given back to the kernel when dropped.
+    ///
+    /// Returns `None` if the completion did not select a buffer.
+    ///
+    /// # Safety
+    ///
+    /// `cqe` must be the completion of a request that selected a buffer from this ring, and a
+    /// completion must not be passed more than once.
+    pub unsafe fn get_buf(&self, cqe: &cqueue::Entry) -> Option<Buf> {
+        let bid = cqueue::buffer_select(cqe.flags())?;
+
+        if bid >= self.inner.buf_cnt {
+            return None;
+        }
+
+        let len = (cqe.result().max(0) as usize).min(self.inner.lens[bid as usize].get() as usize);
+
+        Some(Buf {
+            ring: self.clone(),
+            bid,
+            len,
+        })
+    }
+
+    /// Take the buffers used by the kernel for the completion `cqe` of a bundled request. For a
+    /// receive, the buffers hold the `result` bytes of the completion in order. For a send, they
+    /// are the buffers whose data was sent. The buffers are given back when dropped.
+    ///
+    /// The buffers of a bundle follow each other in the order they were added to the ring, which
+    /// the ring keeps track of, so buffers can be dropped in any order and have any length.
+    ///
+    /// Returns an empty vector if the completion did not select a buffer.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`get_buf`](Self::get_buf).
+    pub unsafe fn get_bufs(&self, cqe: &cqueue::Entry) -> Vec<Buf> {
+        let mut bufs = Vec::new();
+
+        let mut bid = match cqueue::buffer_select(cqe.flags()) {
+            Some(bid) if bid < self.inner.buf_cnt => bid,
+            _ => return bufs,
+        };
+        let mut remaining = cqe.result().max(0) as usize;
+
+        while remaining > 0 && bufs.len() < self.inner.buf_cnt as usize {
+            let len = remaining.min(self.inner.lens[bid as usize].get() as usize);
+            remaining -= len;
+
+            bufs.push(Buf {
+                ring: self.clone(),
+                bid,
+                len,
+            });
+
+            bid = self.inner.next[bid as usize].get();
+        }
+
+        bufs
+    }
+
+    /// Take a buffer held by neither the kernel nor the application, to fill it before handing
+    /// it to the kernel with [`Buf::provide`]. The buffer is empty, with its previous contents
+    /// available through [`Buf::set_len`].
+    ///
+    /// Returns `None` if all buffers are in use, which is always the case unless
+    /// [`Builder::provide_all`] is disabled.
+    pub fn take(&self) -> Option<Buf> {
+        let bid = self.inner.free.borrow_mut().pop()?;
+
+        Some(Buf {
+            ring: self.clone(),
+            bid,
+            len: 0,
+        })
+    }
+}
+
+impl fmt::Debug for BufRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufRing")
+            .field("bgid", &self.inner.bgid)
+            .field("ring_entries", &(self.inner.mask as u32 + 1))
+            .field("buf_cnt", &self.inner.buf_cnt)
+            .field("buf_len", &self.inner.buf_len)
+            .finish()
+    }
+}
+
+/// A buffer taken from a [`BufRing`].
+///
+/// It dereferences to its first [`len`](Self::len) bytes, which are the bytes written by the
+/// kernel for a receive. It goes back to the ring when dropped, or to [`BufRing::take`] if
+/// [`Builder::provide_all`] is disabled.
+pub struct Buf {
+    ring: BufRing,
+    bid: u16,
+    len: usize,
+}
+
+impl Buf {
+    /// The buffer id.
+    pub fn bid(&self) -> u16 {
+        self.bid
+    }
+
+    /// The capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.ring.inner.buf_len
+    }
+
+    /// The length of the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Set the length of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is larger than the [`capacity`](Self::capacity).
+    pub fn set_len(&mut self, len: usize) {
+        assert!(len <= self.capacity(), "len exceeds the buffer capacity");
+        self.len = len;
+    }
+
+    /// Hand the buffer to the kernel, which uses its first [`len`](Self::len) bytes for the next
+    /// request that selects a buffer from the ring, such as a send.
+    pub fn provide(self) {
+        let this = ManuallyDrop::new(self);
+        // Safety: `this` is not used or dropped afterwards.
+        let ring = unsafe { ptr::read(&this.ring) };
+
+        ring.inner.push(this.bid, this.len as _);
+        ring.inner.sync();
+    }
+}
+
+impl Deref for Buf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ring.inner.buf_ptr(self.bid), self.len) }
+    }
+}
+
+impl DerefMut for Buf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.ring.inner.buf_ptr(self.bid), self.len) }
+    }
+}
+
+impl fmt::Debug for Buf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Buf")
+            .field("bgid", &self.ring.inner.bgid)
+            .field("bid", &self.bid)
+            .field("len", &self.len)
+            .field("cap", &self.capacity())
+            .finish()
+    }
+}
+
+impl Drop for Buf {
+    fn drop(&mut self) {
+        let inner = &self.ring.inner;
+
+        if inner.provide_all {
+            inner.push(self.bid, inner.buf_len as _);
+            inner.sync();
+        } else {
+            inner.free.borrow_mut().push(self.bid);
+        }
+    }
+}
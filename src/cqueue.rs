@@ -320,6 +320,38 @@ pub fn buffer_select(flags: u32) -> Option<u16> {
     }
 }
 
+/// Return which dynamic buffers were used by a bundled operation, in order.
+///
+/// With [`Recv::bundle`](crate::opcode::Recv::bundle) and friends, a single completion
+/// covers several consecutive buffers of a provided buffer ring. [`buffer_select`] only
+/// reports the first one, and `result` the total number of bytes, so this assumes that:
+///
+/// - every buffer was added to the ring with a length of exactly `buf_len`, from which the
+///   number of buffers used is derived, and
+/// - buffer ids follow each other in the ring, wrapping around after `buf_cnt`, which holds as
+///   long as the buffers are added back to the ring in the order they were consumed.
+///
+/// The ids are wrong otherwise. [`BufRing::get_bufs`](crate::buf_ring::BufRing::get_bufs)
+/// keeps track of the ring and has neither requirement.
+///
+/// Returns an empty iterator if no buffer was used.
+pub fn bundle_buffer_ids(
+    flags: u32,
+    result: i32,
+    buf_len: u32,
+    buf_cnt: u16,
+) -> impl Iterator<Item = u16> {
+    let (first, count) = match buffer_select(flags) {
+        Some(first) if result > 0 && buf_len > 0 && buf_cnt > 0 => {
+            // `result` is positive, so this rounds up.
+            (first as u32, (result as u32 - 1) / buf_len + 1)
+        }
+        _ => (0, 0),
+    };
+
+    (0..count).map(move |i| ((first + i) % buf_cnt as u32) as u16)
+}
+
 /// Return whether further completion events will be submitted for
 /// this same operation.
 ///
@@ -330,6 +362,17 @@ pub fn more(flags: u32) -> bool {
     flags & sys::IORING_CQE_F_MORE != 0
 }
 
+/// Return whether the buffer used by this operation was only partially consumed.
+///
+/// This corresponds to the `IORING_CQE_F_BUF_MORE` flag, and it signals to the consumer
+/// that the kernel keeps using the rest of the buffer reported by [`buffer_select`] for the
+/// next requests, with a buffer ring registered with
+/// [`BufRingFlags::INC`](crate::types::BufRingFlags::INC). The buffer must not be given
+/// back to the ring until a completion without this flag is posted for it.
+pub fn buf_more(flags: u32) -> bool {
+    flags & sys::IORING_CQE_F_BUF_MORE != 0
+}
+
 /// Return whether socket has more data ready to read.
 ///
 /// This corresponds to the `IORING_CQE_F_SOCK_NONEMPTY` flag, and it signals to
@@ -6,6 +6,7 @@
 
 #[macro_use]
 mod util;
+pub mod buf_ring;
 pub mod cqueue;
 pub mod opcode;
 pub mod register;
@@ -15,9 +16,9 @@ mod sys;
 pub mod types;
 
 use std::marker::PhantomData;
-use std::mem::ManuallyDrop;
+use std::mem::{size_of, ManuallyDrop};
 use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
-use std::{cmp, io, mem};
+use std::{cmp, io, mem, ptr};
 
 #[cfg(feature = "io_safety")]
 use std::os::unix::io::{AsFd, BorrowedFd};
@@ -27,6 +28,7 @@ use cqueue::Sealed as _;
 pub use register::Probe;
 use squeue::Sealed as _;
 pub use squeue::SubmissionQueue;
+use submit::RegisteredRingFd;
 pub use submit::Submitter;
 use util::{Mmap, OwnedFd};
 
@@ -42,6 +44,12 @@ pub struct IoUring<S: squeue::EntryMarker = squeue::Entry, C: cqueue::EntryMarke
     fd: OwnedFd,
     params: Parameters,
     memory: ManuallyDrop<MemoryMap>,
+    /// The ring fd registered with [`Submitter::register_ring_fd`].
+    registered_ring_fd: RegisteredRingFd,
+    /// Whether the queues are kept from child processes, see [`Builder::dontfork`].
+    dontfork: bool,
+    /// Whether a [`resize`](IoUring::resize) failed to map the new queues, leaving stale ones.
+    poisoned: bool,
 }
 
 #[allow(dead_code)]
@@ -51,14 +59,109 @@ struct MemoryMap {
     cq_mmap: Option<Mmap>,
 }
 
+impl MemoryMap {
+    fn dontfork(&self) -> io::Result<()> {
+        self.sq_mmap.dontfork()?;
+        self.sqe_mmap.dontfork()?;
+        if let Some(cq_mmap) = self.cq_mmap.as_ref() {
+            cq_mmap.dontfork()?;
+        }
+        Ok(())
+    }
+}
+
+// The `SMP_CACHE_BYTES` of the kernel, which aligns the SQ array of the queues to it.
+#[cfg(target_arch = "powerpc64")]
+const KERNEL_CACHE_LINE: usize = 128;
+#[cfg(target_arch = "s390x")]
+const KERNEL_CACHE_LINE: usize = 256;
+#[cfg(not(any(target_arch = "powerpc64", target_arch = "s390x")))]
+const KERNEL_CACHE_LINE: usize = 64;
+
+// The layout of the SQ and CQ rings as set up by the kernel, with the CQEs at `cqes_off`. Returns
+// the offset of the SQ array and the length of the rings, which only include the SQ array
+// without `IORING_SETUP_NO_SQARRAY`. With 32 bytes CQEs, the kernel doubles the size of the whole
+// CQ ring rather than only that of the CQEs.
+fn rings_layout(flags: u32, cqes_off: usize, sq_entries: u32, cq_entries: u32) -> (usize, usize) {
+    let mut cq_ring_len = cqes_off + cq_entries as usize * size_of::<sys::io_uring_cqe>();
+    if flags & sys::IORING_SETUP_CQE32 != 0 {
+        cq_ring_len *= 2;
+    }
+    let array = (cq_ring_len + KERNEL_CACHE_LINE - 1) & !(KERNEL_CACHE_LINE - 1);
+    if flags & sys::IORING_SETUP_NO_SQARRAY != 0 {
+        return (array, array);
+    }
+    (array, array + sq_entries as usize * size_of::<u32>())
+}
+
+// NOTE: The `SubmissionQueue` and `CompletionQueue` are references,
+// and their lifetime can never exceed `MemoryMap`.
+//
+// The memory mapped regions of `MemoryMap` never move,
+// so `SubmissionQueue` and `CompletionQueue` are `Unpin`.
+//
+// I really hope that Rust can safely use self-reference types.
+#[inline]
+unsafe fn setup_queue<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
+    fd: &OwnedFd,
+    p: &sys::io_uring_params,
+) -> io::Result<(MemoryMap, squeue::Inner<S>, cqueue::Inner<C>)> {
+    let sq_len = p.sq_off.array as usize + p.sq_entries as usize * mem::size_of::<u32>();
+    let cq_len = p.cq_off.cqes as usize + p.cq_entries as usize * mem::size_of::<C>();
+    let sqe_len = p.sq_entries as usize * mem::size_of::<S>();
+    let sqe_mmap = Mmap::new(fd, sys::IORING_OFF_SQES as _, sqe_len)?;
+
+    if p.features & sys::IORING_FEAT_SINGLE_MMAP != 0 {
+        let scq_mmap = Mmap::new(fd, sys::IORING_OFF_SQ_RING as _, cmp::max(sq_len, cq_len))?;
+
+        let sq = squeue::Inner::new(&scq_mmap, &sqe_mmap, p);
+        let cq = cqueue::Inner::new(&scq_mmap, p);
+        let mm = MemoryMap {
+            sq_mmap: scq_mmap,
+            cq_mmap: None,
+            sqe_mmap,
+        };
+
+        Ok((mm, sq, cq))
+    } else {
+        let sq_mmap = Mmap::new(fd, sys::IORING_OFF_SQ_RING as _, sq_len)?;
+        let cq_mmap = Mmap::new(fd, sys::IORING_OFF_CQ_RING as _, cq_len)?;
+
+        let sq = squeue::Inner::new(&sq_mmap, &sqe_mmap, p);
+        let cq = cqueue::Inner::new(&cq_mmap, p);
+        let mm = MemoryMap {
+            cq_mmap: Some(cq_mmap),
+            sq_mmap,
+            sqe_mmap,
+        };
+
+        Ok((mm, sq, cq))
+    }
+}
+
 /// IoUring build params
 #[derive(Clone, Default)]
 pub struct Builder<S: squeue::EntryMarker = squeue::Entry, C: cqueue::EntryMarker = cqueue::Entry> {
     dontfork: bool,
+    no_mmap: Option<NoMmap>,
     params: sys::io_uring_params,
     phantom: PhantomData<(S, C)>,
 }
 
+/// Where the memory of the queues of a ring set up with `IORING_SETUP_NO_MMAP` comes from.
+#[derive(Clone, Copy)]
+enum NoMmap {
+    /// Allocated when building the ring.
+    Alloc,
+    /// Provided by the application, as the addresses and lengths of the SQEs and of the rings.
+    User {
+        sqes: usize,
+        sqes_len: usize,
+        rings: usize,
+        rings_len: usize,
+    },
+}
+
 /// The parameters that were used to construct an [`IoUring`].
 #[derive(Clone)]
 pub struct Parameters(sys::io_uring_params);
@@ -83,6 +186,7 @@ impl IoUring<squeue::Entry, cqueue::Entry> {
     pub fn builder() -> Builder<squeue::Entry, cqueue::Entry> {
         Builder {
             dontfork: false,
+            no_mmap: None,
             params: sys::io_uring_params {
                 flags: squeue::Entry::ADDITIONAL_FLAGS | cqueue::Entry::ADDITIONAL_FLAGS,
                 ..Default::default()
@@ -115,6 +219,7 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
     pub fn generic_builder() -> Builder<S, C> {
         Builder {
             dontfork: false,
+            no_mmap: None,
             params: sys::io_uring_params {
                 flags: S::ADDITIONAL_FLAGS | C::ADDITIONAL_FLAGS,
                 ..Default::default()
@@ -124,56 +229,87 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
     }
 
     fn with_params(entries: u32, mut p: sys::io_uring_params) -> io::Result<Self> {
-        // NOTE: The `SubmissionQueue` and `CompletionQueue` are references,
-        // and their lifetime can never exceed `MemoryMap`.
-        //
-        // The memory mapped regions of `MemoryMap` never move,
-        // so `SubmissionQueue` and `CompletionQueue` are `Unpin`.
-        //
-        // I really hope that Rust can safely use self-reference types.
-        #[inline]
-        unsafe fn setup_queue<S: squeue::EntryMarker, C: cqueue::EntryMarker>(
-            fd: &OwnedFd,
-            p: &sys::io_uring_params,
-        ) -> io::Result<(MemoryMap, squeue::Inner<S>, cqueue::Inner<C>)> {
-            let sq_len = p.sq_off.array as usize + p.sq_entries as usize * mem::size_of::<u32>();
-            let cq_len = p.cq_off.cqes as usize + p.cq_entries as usize * mem::size_of::<C>();
-            let sqe_len = p.sq_entries as usize * mem::size_of::<S>();
-            let sqe_mmap = Mmap::new(fd, sys::IORING_OFF_SQES as _, sqe_len)?;
-
-            if p.features & sys::IORING_FEAT_SINGLE_MMAP != 0 {
-                let scq_mmap =
-                    Mmap::new(fd, sys::IORING_OFF_SQ_RING as _, cmp::max(sq_len, cq_len))?;
-
-                let sq = squeue::Inner::new(&scq_mmap, &sqe_mmap, p);
-                let cq = cqueue::Inner::new(&scq_mmap, p);
-                let mm = MemoryMap {
-                    sq_mmap: scq_mmap,
-                    cq_mmap: None,
-                    sqe_mmap,
-                };
+        let fd: OwnedFd =
+            unsafe { sys::io_uring_setup(entries, &mut p).map(|fd| OwnedFd::from_raw_fd(fd))? };
 
-                Ok((mm, sq, cq))
-            } else {
-                let sq_mmap = Mmap::new(fd, sys::IORING_OFF_SQ_RING as _, sq_len)?;
-                let cq_mmap = Mmap::new(fd, sys::IORING_OFF_CQ_RING as _, cq_len)?;
-
-                let sq = squeue::Inner::new(&sq_mmap, &sqe_mmap, p);
-                let cq = cqueue::Inner::new(&cq_mmap, p);
-                let mm = MemoryMap {
-                    cq_mmap: Some(cq_mmap),
-                    sq_mmap,
-                    sqe_mmap,
+        let (mm, sq, cq) = unsafe { setup_queue(&fd, &p)? };
+
+        Ok(IoUring {
+            sq,
+            cq,
+            fd,
+            params: Parameters(p),
+            memory: ManuallyDrop::new(mm),
+            registered_ring_fd: RegisteredRingFd::new(),
+            dontfork: false,
+            poisoned: false,
+        })
+    }
+
+    fn with_memory(
+        entries: u32,
+        mut p: sys::io_uring_params,
+        no_mmap: NoMmap,
+        (sqes_len, rings_len): (usize, usize),
+    ) -> io::Result<Self> {
+        let (sqes, rings) = match no_mmap {
+            NoMmap::Alloc => {
+                // The rings follow the SQEs in the same allocation, at the next page. Before 6.13,
+                // the kernel requires each of them to fit in a single page or huge page.
+                let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+                let rings_off = (sqes_len + page_size - 1) & !(page_size - 1);
+                let len = rings_off + rings_len;
+
+                let sqes = if sqes_len > page_size || rings_len > page_size {
+                    Mmap::new_anonymous_huge(len).or_else(|_| Mmap::new_anonymous(len))?
+                } else {
+                    Mmap::new_anonymous(len)?
+                };
+                let rings = unsafe {
+                    let addr = sqes.offset(rings_off as _);
+                    Mmap::from_raw(ptr::NonNull::new_unchecked(addr), rings_len)
                 };
 
-                Ok((mm, sq, cq))
+                (sqes, rings)
             }
-        }
+            NoMmap::User {
+                sqes,
+                sqes_len: user_sqes_len,
+                rings,
+                rings_len: user_rings_len,
+            } => {
+                if user_sqes_len < sqes_len || user_rings_len < rings_len {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "memory too small for the queues",
+                    ));
+                }
+
+                // The validity of the memory is guaranteed by the caller of
+                // `Builder::setup_no_mmap_with_memory`.
+                unsafe {
+                    (
+                        Mmap::from_raw(ptr::NonNull::new_unchecked(sqes as _), user_sqes_len),
+                        Mmap::from_raw(ptr::NonNull::new_unchecked(rings as _), user_rings_len),
+                    )
+                }
+            }
+        };
+
+        p.flags |= sys::IORING_SETUP_NO_MMAP;
+        p.sq_off.user_addr = sqes.as_mut_ptr() as _;
+        p.cq_off.user_addr = rings.as_mut_ptr() as _;
 
         let fd: OwnedFd =
             unsafe { sys::io_uring_setup(entries, &mut p).map(|fd| OwnedFd::from_raw_fd(fd))? };
 
-        let (mm, sq, cq) = unsafe { setup_queue(&fd, &p)? };
+        let sq = unsafe { squeue::Inner::new(&rings, &sqes, &p) };
+        let cq = unsafe { cqueue::Inner::new(&rings, &p) };
+        let mm = MemoryMap {
+            sq_mmap: rings,
+            cq_mmap: None,
+            sqe_mmap: sqes,
+        };
 
         Ok(IoUring {
             sq,
@@ -181,6 +317,9 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
             fd,
             params: Parameters(p),
             memory: ManuallyDrop::new(mm),
+            registered_ring_fd: RegisteredRingFd::new(),
+            dontfork: false,
+            poisoned: false,
         })
     }
 
@@ -188,9 +327,16 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
     /// events to the kernel for execution and to register files or buffers with it.
     #[inline]
     pub fn submitter(&self) -> Submitter<'_> {
+        self.check_poisoned();
+        self.submitter_unchecked()
+    }
+
+    #[inline]
+    fn submitter_unchecked(&self) -> Submitter<'_> {
         Submitter::new(
             &self.fd,
             &self.params,
+            &self.registered_ring_fd,
             self.sq.head,
             self.sq.tail,
             self.sq.flags,
@@ -203,6 +349,107 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
         &self.params
     }
 
+    /// Resize the submission queue to `sq_entries` and the completion queue to `cq_entries`
+    /// entries, which are rounded up to powers of two. `cq_entries` must be at least
+    /// `sq_entries`. Entries that are pending in either queue are carried over to the new queues,
+    /// and resizing fails with `EOVERFLOW` if they do not fit.
+    ///
+    /// The queues are mapped again afterwards. Borrowing `self` mutably ensures that no
+    /// [`SubmissionQueue`], [`CompletionQueue`] or [`Submitter`] obtained earlier is still
+    /// around, as they point into the old queues. The queues obtained from the unsafe
+    /// [`submission_shared`](Self::submission_shared) and
+    /// [`completion_shared`](Self::completion_shared) must not be used across a resize either.
+    /// If mapping the new queues fails, the instance can no longer be used: any further use
+    /// panics, except for dropping it.
+    ///
+    /// This requires the ring to be set up with [`Builder::setup_defer_taskrun`], and fails with
+    /// `EINVAL` otherwise. Rings set up with [`Builder::setup_no_mmap`] cannot be resized.
+    ///
+    /// Available since 6.13.
+    pub fn resize(&mut self, sq_entries: u32, cq_entries: u32) -> io::Result<()> {
+        self.check_poisoned();
+
+        if self.params.is_setup_no_mmap() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the queues of rings set up without mmap cannot be resized",
+            ));
+        }
+
+        // The kernel does not report where the SQ array of the new queues is, so it is computed
+        // below. Check the computation against the current queues first, so that an unexpected
+        // layout fails before the kernel switches to the new queues.
+        let has_array = !self.params.is_setup_no_sqarray();
+        if has_array {
+            let p = &self.params.0;
+            let (array, _) = rings_layout(p.flags, p.cq_off.cqes as _, p.sq_entries, p.cq_entries);
+            if array != p.sq_off.array as usize {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "unexpected layout of the queues, cannot locate the SQ array after resizing",
+                ));
+            }
+        }
+
+        let mut p = sys::io_uring_params {
+            sq_entries,
+            cq_entries,
+            flags: sys::IORING_SETUP_CQSIZE,
+            ..Default::default()
+        };
+
+        register::execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_RESIZE_RINGS,
+            &mut p as *mut sys::io_uring_params as *const _,
+            1,
+        )?;
+
+        // Only the sizes and the layout of the queues change, the rest is kept from the setup.
+        let mut params = self.params.0;
+        params.sq_entries = p.sq_entries;
+        params.cq_entries = p.cq_entries;
+        params.sq_off = p.sq_off;
+        params.cq_off = p.cq_off;
+
+        if params.sq_off.array == 0 && has_array {
+            let (array, _) =
+                rings_layout(params.flags, p.cq_off.cqes as _, p.sq_entries, p.cq_entries);
+            params.sq_off.array = array as _;
+        }
+
+        // The kernel already switched to the new queues, the old ones are stale.
+        let (mm, sq, cq) = match unsafe { setup_queue(&self.fd, &params) } {
+            Ok(queues) => queues,
+            Err(err) => {
+                self.poisoned = true;
+                return Err(err);
+            }
+        };
+
+        self.sq = sq;
+        self.cq = cq;
+        self.params = Parameters(params);
+
+        // The new queues are mapped, the old ones can go.
+        let memory = mem::replace(&mut self.memory, ManuallyDrop::new(mm));
+        drop(ManuallyDrop::into_inner(memory));
+
+        if self.dontfork {
+            self.memory.dontfork()?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn check_poisoned(&self) {
+        assert!(
+            !self.poisoned,
+            "io_uring instance used after failing to map its resized queues"
+        );
+    }
+
     /// Initiate asynchronous I/O. See [`Submitter::submit`] for more details.
     #[inline]
     pub fn submit(&self) -> io::Result<usize> {
@@ -230,9 +477,11 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
         SubmissionQueue<'_, S>,
         CompletionQueue<'_, C>,
     ) {
+        self.check_poisoned();
         let submit = Submitter::new(
             &self.fd,
             &self.params,
+            &self.registered_ring_fd,
             self.sq.head,
             self.sq.tail,
             self.sq.flags,
@@ -244,6 +493,7 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
     /// kernel.
     #[inline]
     pub fn submission(&mut self) -> SubmissionQueue<'_, S> {
+        self.check_poisoned();
         self.sq.borrow()
     }
 
@@ -254,6 +504,7 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
     /// No other [`SubmissionQueue`]s may exist when calling this function.
     #[inline]
     pub unsafe fn submission_shared(&self) -> SubmissionQueue<'_, S> {
+        self.check_poisoned();
         self.sq.borrow_shared()
     }
 
@@ -261,6 +512,7 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
     /// events from the kernel.
     #[inline]
     pub fn completion(&mut self) -> CompletionQueue<'_, C> {
+        self.check_poisoned();
         self.cq.borrow()
     }
 
@@ -271,12 +523,19 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> IoUring<S, C> {
     /// No other [`CompletionQueue`]s may exist when calling this function.
     #[inline]
     pub unsafe fn completion_shared(&self) -> CompletionQueue<'_, C> {
+        self.check_poisoned();
         self.cq.borrow_shared()
     }
 }
 
 impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> Drop for IoUring<S, C> {
     fn drop(&mut self) {
+        // The registered ring fd holds a reference to the ring, which is released when the
+        // registering thread exits otherwise. Only that thread can release it now.
+        if self.registered_ring_fd.get().is_some() {
+            let _ = self.submitter_unchecked().unregister_ring_fd();
+        }
+
         // Ensure that `MemoryMap` is released before `fd`.
         unsafe {
             ManuallyDrop::drop(&mut self.memory);
@@ -419,6 +678,19 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> Builder<S, C> {
         self
     }
 
+    /// Do not use the indirection array of the submission queue, which lets the kernel read each
+    /// SQE directly at its position in the queue rather than through an index loaded from the
+    /// array. The crate only writes the array once, when the queue is created, to map every
+    /// position to the SQE at that same position. Submitting is therefore unchanged on the
+    /// application side, and the savings are the kernel's load of an array entry for each SQE,
+    /// and the memory of the array.
+    ///
+    /// Available since 6.6, and building the ring fails with `EINVAL` on earlier kernels.
+    pub fn setup_no_sqarray(&mut self) -> &mut Self {
+        self.params.flags |= sys::IORING_SETUP_NO_SQARRAY;
+        self
+    }
+
     /// Hint the kernel that a single task will submit requests. Used for optimizations. This is
     /// enforced by the kernel, and request that don't respect that will fail with -EEXIST.
     /// If [`Builder::setup_sqpoll`] is enabled, the polling task is doing the submissions and multiple
@@ -428,17 +700,92 @@ impl<S: squeue::EntryMarker, C: cqueue::EntryMarker> Builder<S, C> {
         self
     }
 
+    /// Place the queues in memory allocated by this crate rather than by the kernel, using a 2 MiB
+    /// huge page when they do not fit in regular pages and the system has huge pages reserved.
+    /// Kernels that do not support this, before 6.5, allocate the queues themselves instead, as
+    /// reported by [`Parameters::is_setup_no_mmap`].
+    pub fn setup_no_mmap(&mut self) -> &mut Self {
+        self.no_mmap = Some(NoMmap::Alloc);
+        self
+    }
+
+    /// Place the queues in memory provided by the application rather than allocated by the
+    /// kernel: the SQEs in the `sqes_len` bytes at `sqes`, and the SQ and CQ rings in the
+    /// `rings_len` bytes at `rings`. The lengths needed for a number of entries are returned by
+    /// [`no_mmap_memory_len`](Self::no_mmap_memory_len), and building the ring fails with
+    /// [`InvalidInput`](io::ErrorKind::InvalidInput) if the memory is too small. Before 6.13,
+    /// the kernel requires each region to fit in a single page or huge page.
+    ///
+    /// Available since 6.5. Unlike [`setup_no_mmap`](Self::setup_no_mmap), building the ring
+    /// fails on kernels that do not support this.
+    ///
+    /// # Safety
+    ///
+    /// Both regions must be page aligned, zero-filled, valid for reads and writes, and not
+    /// overlap. At most one ring can be built from them: [`build`](Self::build) must not be
+    /// called again on this builder or its clones until that ring is dropped, and the regions
+    /// must be zero-filled again before building another ring. They must not be accessed by the
+    /// application or used for anything else until the ring is dropped.
+    pub unsafe fn setup_no_mmap_with_memory(
+        &mut self,
+        sqes: *mut libc::c_void,
+        sqes_len: usize,
+        rings: *mut libc::c_void,
+        rings_len: usize,
+    ) -> &mut Self {
+        self.no_mmap = Some(NoMmap::User {
+            sqes: sqes as _,
+            sqes_len,
+            rings: rings as _,
+            rings_len,
+        });
+        self
+    }
+
+    /// The lengths of the memory regions needed by
+    /// [`setup_no_mmap_with_memory`](Self::setup_no_mmap_with_memory) to build a ring with
+    /// `entries` entries, as a tuple of the length for the SQEs and for the rings.
+    pub fn no_mmap_memory_len(&self, entries: u32) -> (usize, usize) {
+        // Mirror the sizes picked by the kernel, with the number of entries rounded up to a
+        // power of two.
+        let sq_entries = entries.min(1 << 15).next_power_of_two();
+        let cq_entries = if self.params.flags & sys::IORING_SETUP_CQSIZE != 0 {
+            self.params.cq_entries.min(1 << 16).next_power_of_two()
+        } else {
+            sq_entries * 2
+        };
+
+        // The header of the rings fits in a cache line, which the CQEs are aligned to.
+        let (_, rings_len) =
+            rings_layout(self.params.flags, KERNEL_CACHE_LINE, sq_entries, cq_entries);
+
+        (sq_entries as usize * size_of::<S>(), rings_len)
+    }
+
     /// Build an [IoUring], with the specified number of entries in the submission queue and
     /// completion queue unless [`setup_cqsize`](Self::setup_cqsize) has been called.
     pub fn build(&self, entries: u32) -> io::Result<IoUring<S, C>> {
-        let ring = IoUring::with_params(entries, self.params)?;
+        let mut ring = match self.no_mmap {
+            Some(no_mmap) => {
+                let len = self.no_mmap_memory_len(entries);
+                match IoUring::with_memory(entries, self.params, no_mmap, len) {
+                    // Let kernels without `IORING_SETUP_NO_MMAP` allocate the queues instead,
+                    // unless the application provided the memory.
+                    Err(ref err)
+                        if err.raw_os_error() == Some(libc::EINVAL)
+                            && matches!(no_mmap, NoMmap::Alloc) =>
+                    {
+                        IoUring::with_params(entries, self.params)?
+                    }
+                    res => res?,
+                }
+            }
+            None => IoUring::with_params(entries, self.params)?,
+        };
 
         if self.dontfork {
-            ring.memory.sq_mmap.dontfork()?;
-            ring.memory.sqe_mmap.dontfork()?;
-            if let Some(cq_mmap) = ring.memory.cq_mmap.as_ref() {
-                cq_mmap.dontfork()?;
-            }
+            ring.memory.dontfork()?;
+            ring.dontfork = true;
         }
 
         Ok(ring)
@@ -462,6 +809,18 @@ impl Parameters {
         self.0.flags & sys::IORING_SETUP_SINGLE_ISSUER != 0
     }
 
+    /// Whether the submission queue was set up without its indirection array, see
+    /// [`Builder::setup_no_sqarray`].
+    pub fn is_setup_no_sqarray(&self) -> bool {
+        self.0.flags & sys::IORING_SETUP_NO_SQARRAY != 0
+    }
+
+    /// Whether the queues were placed in memory provided when building the ring rather than
+    /// allocated by the kernel, see [`Builder::setup_no_mmap`].
+    pub fn is_setup_no_mmap(&self) -> bool {
+        self.0.flags & sys::IORING_SETUP_NO_MMAP != 0
+    }
+
     /// If this flag is set, the SQ and CQ rings were mapped with a single `mmap(2)` call. This
     /// means that only two syscalls were used instead of three.
     pub fn is_feature_single_mmap(&self) -> bool {
@@ -595,6 +954,15 @@ impl Parameters {
         self.0.features & sys::IORING_FEAT_LINKED_FILE != 0
     }
 
+    /// Whether the kernel supports bundled sends and receives.
+    ///
+    /// If this flag is set, then [`Send`](opcode::Send), [`Recv`](opcode::Recv) and
+    /// [`RecvMulti`](opcode::RecvMulti) can use several provided buffers in a single completion,
+    /// see their `bundle` option. Available since kernel 6.10.
+    pub fn is_feature_recvsend_bundle(&self) -> bool {
+        self.0.features & sys::IORING_FEAT_RECVSEND_BUNDLE != 0
+    }
+
     /// The number of submission queue entries allocated.
     pub fn sq_entries(&self) -> u32 {
         self.0.sq_entries
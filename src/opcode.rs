@@ -362,19 +362,27 @@ opcode!(
         fd: { impl sealed::UseFixed },
         flags: { u32 },
         ;;
-        multi: bool = false
+        multi: bool = false,
+        /// Use level-triggered instead of edge-triggered notification,
+        /// available since 6.0. It was disabled again in 6.2, and kernels that
+        /// do not accept this flag for poll requests complete them with
+        /// `-libc::EINVAL`.
+        level: bool = false
     }
 
     pub const CODE = sys::IORING_OP_POLL_ADD;
 
     pub fn build(self) -> Entry {
-        let PollAdd { fd, flags, multi } = self;
+        let PollAdd { fd, flags, multi, level } = self;
 
         let mut sqe = sqe_zeroed();
         sqe.opcode = Self::CODE;
         assign_fd!(sqe.fd = fd);
         if multi {
-            sqe.len = sys::IORING_POLL_ADD_MULTI;
+            sqe.len |= sys::IORING_POLL_ADD_MULTI;
+        }
+        if level {
+            sqe.len |= sys::IORING_POLL_ADD_LEVEL;
         }
 
         #[cfg(target_endian = "little")] {
@@ -416,6 +424,61 @@ opcode!(
     }
 );
 
+opcode!(
+    /// Update the event mask and/or user data of an existing [poll](PollAdd) request
+    /// in place, available since 5.13.
+    ///
+    /// If found, the `result` method of the `cqueue::Entry` will return 0.
+    /// If not found, `result` will return `-libc::ENOENT`.
+    #[derive(Debug)]
+    pub struct PollUpdate {
+        /// `old_user_data` is the user data of the poll request to update.
+        old_user_data: { u64 }
+        ;;
+        /// Replace the user data of the poll request.
+        new_user_data: Option<u64> = None,
+        /// Replace the event mask of the poll request, see [`PollAdd`].
+        flags: Option<u32> = None,
+        /// Whether the poll request is multishot after the update. Only takes effect
+        /// together with `flags`; updating the event mask of a multishot poll without
+        /// setting this turns it into a single-shot poll.
+        multi: bool = false
+    }
+
+    pub const CODE = sys::IORING_OP_POLL_REMOVE;
+
+    pub fn build(self) -> Entry {
+        let PollUpdate { old_user_data, new_user_data, flags, multi } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = -1;
+        sqe.__bindgen_anon_2.addr = old_user_data as _;
+        if let Some(new_user_data) = new_user_data {
+            sqe.len |= sys::IORING_POLL_UPDATE_USER_DATA;
+            sqe.__bindgen_anon_1.off = new_user_data;
+        }
+        if let Some(flags) = flags {
+            sqe.len |= sys::IORING_POLL_UPDATE_EVENTS;
+            if multi {
+                sqe.len |= sys::IORING_POLL_ADD_MULTI;
+            }
+
+            #[cfg(target_endian = "little")] {
+                sqe.__bindgen_anon_3.poll32_events = flags;
+            }
+
+            #[cfg(target_endian = "big")] {
+                let x = flags << 16;
+                let y = flags >> 16;
+                let flags = x | y;
+                sqe.__bindgen_anon_3.poll32_events = flags;
+            }
+        }
+        Entry(sqe)
+    }
+);
+
 opcode!(
     /// Sync a file segment with disk, equivalent to `sync_file_range(2)`.
     #[derive(Debug)]
@@ -459,18 +522,24 @@ opcode!(
         msg: { *const libc::msghdr },
         ;;
         ioprio: u16 = 0,
-        flags: u32 = 0
+        flags: u32 = 0,
+        /// Skip the initial attempt and arm poll straight away, for sockets that are known to
+        /// have no data (or space) yet. Available since 5.19.
+        poll_first: bool = false
     }
 
     pub const CODE = sys::IORING_OP_SENDMSG;
 
     pub fn build(self) -> Entry {
-        let SendMsg { fd, msg, ioprio, flags } = self;
+        let SendMsg { fd, msg, ioprio, flags, poll_first } = self;
 
         let mut sqe = sqe_zeroed();
         sqe.opcode = Self::CODE;
         assign_fd!(sqe.fd = fd);
         sqe.ioprio = ioprio;
+        if poll_first {
+            sqe.ioprio |= sys::IORING_RECVSEND_POLL_FIRST as u16;
+        }
         sqe.__bindgen_anon_2.addr = msg as _;
         sqe.len = 1;
         sqe.__bindgen_anon_3.msg_flags = flags;
@@ -489,18 +558,24 @@ opcode!(
         ;;
         ioprio: u16 = 0,
         flags: u32 = 0,
-        buf_group: u16 = 0
+        buf_group: u16 = 0,
+        /// Skip the initial attempt and arm poll straight away, for sockets that are known to
+        /// have no data (or space) yet. Available since 5.19.
+        poll_first: bool = false
     }
 
     pub const CODE = sys::IORING_OP_RECVMSG;
 
     pub fn build(self) -> Entry {
-        let RecvMsg { fd, msg, ioprio, flags, buf_group } = self;
+        let RecvMsg { fd, msg, ioprio, flags, buf_group, poll_first } = self;
 
         let mut sqe = sqe_zeroed();
         sqe.opcode = Self::CODE;
         assign_fd!(sqe.fd = fd);
         sqe.ioprio = ioprio;
+        if poll_first {
+            sqe.ioprio |= sys::IORING_RECVSEND_POLL_FIRST as u16;
+        }
         sqe.__bindgen_anon_2.addr = msg as _;
         sqe.len = 1;
         sqe.__bindgen_anon_3.msg_flags = flags;
@@ -682,11 +757,12 @@ opcode!(
 
 opcode!(
     /// Attempt to cancel an already issued request.
+    ///
+    /// See [`AsyncCancel2`] to match requests by file descriptor, or to cancel more than one
+    /// request at a time.
     pub struct AsyncCancel {
         user_data: { u64 }
         ;;
-
-        // TODO flags
     }
 
     pub const CODE = sys::IORING_OP_ASYNC_CANCEL;
@@ -1099,18 +1175,32 @@ opcode!(
 
 opcode!(
     /// Send a message on a socket, equivalent to `send(2)`.
+    ///
+    /// The kernel does not accept registered buffers for this operation, use [`SendZc`] or
+    /// [`WriteFixed`] to send from a registered buffer instead.
     pub struct Send {
         fd: { impl sealed::UseFixed },
         buf: { *const u8 },
         len: { u32 },
         ;;
-        flags: i32 = 0
+        flags: i32 = 0,
+        /// The provided buffer ring to send from, used when
+        /// [`BUFFER_SELECT`](crate::squeue::Flags::BUFFER_SELECT) is set. Available since 6.10.
+        buf_group: u16 = 0,
+        /// Skip the initial attempt and arm poll straight away, for sockets that are known to
+        /// have no data (or space) yet. Available since 5.19.
+        poll_first: bool = false,
+        /// Send from several buffers of `buf_group` at once rather than from a single one, up to
+        /// `len` bytes if it is not zero. The buffers used are reported by
+        /// [`cqueue::bundle_buffer_ids`](crate::cqueue::bundle_buffer_ids). Available since 6.10,
+        /// see [`Parameters::is_feature_recvsend_bundle`](crate::Parameters::is_feature_recvsend_bundle).
+        bundle: bool = false
     }
 
     pub const CODE = sys::IORING_OP_SEND;
 
     pub fn build(self) -> Entry {
-        let Send { fd, buf, len, flags } = self;
+        let Send { fd, buf, len, flags, buf_group, poll_first, bundle } = self;
 
         let mut sqe = sqe_zeroed();
         sqe.opcode = Self::CODE;
@@ -1118,25 +1208,44 @@ opcode!(
         sqe.__bindgen_anon_2.addr = buf as _;
         sqe.len = len;
         sqe.__bindgen_anon_3.msg_flags = flags as _;
+        sqe.__bindgen_anon_4.buf_group = buf_group;
+        if poll_first {
+            sqe.ioprio |= sys::IORING_RECVSEND_POLL_FIRST as u16;
+        }
+        if bundle {
+            sqe.ioprio |= sys::IORING_RECVSEND_BUNDLE as u16;
+        }
         Entry(sqe)
     }
 );
 
 opcode!(
     /// Receive a message from a socket, equivalent to `recv(2)`.
+    ///
+    /// The kernel does not accept registered buffers for this operation, use [`ReadFixed`] to
+    /// receive into a registered buffer instead.
     pub struct Recv {
         fd: { impl sealed::UseFixed },
         buf: { *mut u8 },
         len: { u32 },
         ;;
         flags: i32 = 0,
-        buf_group: u16 = 0
+        buf_group: u16 = 0,
+        /// Skip the initial attempt and arm poll straight away, for sockets that are known to
+        /// have no data (or space) yet. Available since 5.19.
+        poll_first: bool = false,
+        /// Receive into several buffers of the `buf_group` provided buffer ring at once rather than
+        /// into a single one, which requires [`BUFFER_SELECT`](crate::squeue::Flags::BUFFER_SELECT).
+        /// The buffers used are reported by
+        /// [`cqueue::bundle_buffer_ids`](crate::cqueue::bundle_buffer_ids). Available since 6.10,
+        /// see [`Parameters::is_feature_recvsend_bundle`](crate::Parameters::is_feature_recvsend_bundle).
+        bundle: bool = false
     }
 
     pub const CODE = sys::IORING_OP_RECV;
 
     pub fn build(self) -> Entry {
-        let Recv { fd, buf, len, flags, buf_group } = self;
+        let Recv { fd, buf, len, flags, buf_group, poll_first, bundle } = self;
 
         let mut sqe = sqe_zeroed();
         sqe.opcode = Self::CODE;
@@ -1145,6 +1254,12 @@ opcode!(
         sqe.len = len;
         sqe.__bindgen_anon_3.msg_flags = flags as _;
         sqe.__bindgen_anon_4.buf_group = buf_group;
+        if poll_first {
+            sqe.ioprio |= sys::IORING_RECVSEND_POLL_FIRST as u16;
+        }
+        if bundle {
+            sqe.ioprio |= sys::IORING_RECVSEND_BUNDLE as u16;
+        }
         Entry(sqe)
     }
 );
@@ -1170,12 +1285,18 @@ opcode!(
         buf_group: { u16 },
         ;;
         flags: i32 = 0,
+        /// Fill several buffers of the group in each completion rather than a single one. The
+        /// buffers used are reported by
+        /// [`cqueue::bundle_buffer_ids`](crate::cqueue::bundle_buffer_ids). Available since 6.10,
+        /// with provided buffer rings only, see
+        /// [`Parameters::is_feature_recvsend_bundle`](crate::Parameters::is_feature_recvsend_bundle).
+        bundle: bool = false,
     }
 
     pub const CODE = sys::IORING_OP_RECV;
 
     pub fn build(self) -> Entry {
-        let RecvMulti { fd, buf_group, flags } = self;
+        let RecvMulti { fd, buf_group, flags, bundle } = self;
 
         let mut sqe = sqe_zeroed();
         sqe.opcode = Self::CODE;
@@ -1184,6 +1305,9 @@ opcode!(
         sqe.__bindgen_anon_4.buf_group = buf_group;
         sqe.flags |= 1 << sys::IOSQE_BUFFER_SELECT_BIT;
         sqe.ioprio = sys::IORING_RECV_MULTISHOT as _;
+        if bundle {
+            sqe.ioprio |= sys::IORING_RECVSEND_BUNDLE as u16;
+        }
         Entry(sqe)
     }
 );
@@ -1452,6 +1576,35 @@ opcode!(
     }
 );
 
+opcode!(
+    /// Update the expiration of an existing [timeout operation](Timeout) in place,
+    /// without removing and re-adding it.
+    ///
+    /// If found, the `result` method of the `cqueue::Entry` will return 0.
+    /// If not found, `result` will return `-libc::ENOENT`.
+    pub struct TimeoutUpdate {
+        user_data: { u64 },
+        timespec: { *const types::Timespec },
+        ;;
+        /// `flags` may contain [types::TimeoutFlags::ABS] for an absolute timeout value, or 0 for a relative timeout.
+        flags: types::TimeoutFlags = types::TimeoutFlags::empty()
+    }
+
+    pub const CODE = sys::IORING_OP_TIMEOUT_REMOVE;
+
+    pub fn build(self) -> Entry {
+        let TimeoutUpdate { user_data, timespec, flags } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = -1;
+        sqe.__bindgen_anon_2.addr = user_data as _;
+        sqe.__bindgen_anon_1.addr2 = timespec as _;
+        sqe.__bindgen_anon_3.timeout_flags = (flags | types::TimeoutFlags::UPDATE).bits();
+        Entry(sqe)
+    }
+);
+
 // === 5.15 ===
 
 opcode!(
@@ -1527,6 +1680,37 @@ opcode!(
     }
 );
 
+opcode!(
+    /// Update the expiration of an existing [link timeout](LinkTimeout) in place.
+    ///
+    /// If found, the `result` method of the `cqueue::Entry` will return 0.
+    /// If not found, `result` will return `-libc::ENOENT`.
+    pub struct LinkTimeoutUpdate {
+        user_data: { u64 },
+        timespec: { *const types::Timespec },
+        ;;
+        /// `flags` may contain [types::TimeoutFlags::ABS] for an absolute timeout value, or 0 for a relative timeout.
+        flags: types::TimeoutFlags = types::TimeoutFlags::empty()
+    }
+
+    pub const CODE = sys::IORING_OP_TIMEOUT_REMOVE;
+
+    pub fn build(self) -> Entry {
+        let LinkTimeoutUpdate { user_data, timespec, flags } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = -1;
+        sqe.__bindgen_anon_2.addr = user_data as _;
+        sqe.__bindgen_anon_1.addr2 = timespec as _;
+        sqe.__bindgen_anon_3.timeout_flags = (flags
+            | types::TimeoutFlags::UPDATE
+            | types::TimeoutFlags::LINK_TIMEOUT_UPDATE)
+            .bits();
+        Entry(sqe)
+    }
+);
+
 // === 5.18 ===
 
 opcode!(
@@ -1562,6 +1746,145 @@ opcode!(
 
 // === 5.19 ===
 
+opcode!(
+    /// Set an extended attribute value of an open file, equivalent to `fsetxattr(2)`.
+    pub struct FSetXattr {
+        fd: { impl sealed::UseFixed },
+        name: { *const libc::c_char },
+        value: { *const libc::c_void },
+        len: { u32 },
+        ;;
+        /// `flags` may contain `XATTR_CREATE` or `XATTR_REPLACE`, see `setxattr(2)`.
+        flags: i32 = 0
+    }
+
+    pub const CODE = sys::IORING_OP_FSETXATTR;
+
+    pub fn build(self) -> Entry {
+        let FSetXattr { fd, name, value, len, flags } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_2.addr = name as _;
+        sqe.__bindgen_anon_1.off = value as _;
+        sqe.len = len;
+        sqe.__bindgen_anon_3.xattr_flags = flags as _;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Set an extended attribute value of a file, equivalent to `setxattr(2)`.
+    pub struct SetXattr {
+        name: { *const libc::c_char },
+        value: { *const libc::c_void },
+        path: { *const libc::c_char },
+        len: { u32 },
+        ;;
+        /// `flags` may contain `XATTR_CREATE` or `XATTR_REPLACE`, see `setxattr(2)`.
+        flags: i32 = 0
+    }
+
+    pub const CODE = sys::IORING_OP_SETXATTR;
+
+    pub fn build(self) -> Entry {
+        let SetXattr { name, value, path, len, flags } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.__bindgen_anon_2.addr = name as _;
+        sqe.__bindgen_anon_1.off = value as _;
+        unsafe { sqe.__bindgen_anon_6.__bindgen_anon_1.as_mut().addr3 = path as _ };
+        sqe.len = len;
+        sqe.__bindgen_anon_3.xattr_flags = flags as _;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Get an extended attribute value of an open file, equivalent to `fgetxattr(2)`.
+    ///
+    /// On success, the `result` method of the `cqueue::Entry` returns the size of the value.
+    pub struct FGetXattr {
+        fd: { impl sealed::UseFixed },
+        name: { *const libc::c_char },
+        value: { *mut libc::c_void },
+        len: { u32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_FGETXATTR;
+
+    pub fn build(self) -> Entry {
+        let FGetXattr { fd, name, value, len } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_2.addr = name as _;
+        sqe.__bindgen_anon_1.off = value as _;
+        sqe.len = len;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Get an extended attribute value of a file, equivalent to `getxattr(2)`.
+    ///
+    /// On success, the `result` method of the `cqueue::Entry` returns the size of the value.
+    pub struct GetXattr {
+        name: { *const libc::c_char },
+        value: { *mut libc::c_void },
+        path: { *const libc::c_char },
+        len: { u32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_GETXATTR;
+
+    pub fn build(self) -> Entry {
+        let GetXattr { name, value, path, len } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.__bindgen_anon_2.addr = name as _;
+        sqe.__bindgen_anon_1.off = value as _;
+        unsafe { sqe.__bindgen_anon_6.__bindgen_anon_1.as_mut().addr3 = path as _ };
+        sqe.len = len;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Attempt to cancel already issued requests matching the criteria of a
+    /// [`CancelBuilder`](types::CancelBuilder).
+    ///
+    /// Requests can be matched by `user_data`, by the file descriptor ([`types::Fd`] or
+    /// [`types::Fixed`]) they were issued against, or regardless of either. When
+    /// [`CancelBuilder::all`](types::CancelBuilder::all) or
+    /// [`CancelBuilder::any`](types::CancelBuilder::any) is used, the `result` method of the
+    /// `cqueue::Entry` returns the number of cancelled requests, which may be 0. Otherwise it
+    /// returns 0 on success, or `-libc::ENOENT` if no request matched.
+    pub struct AsyncCancel2 {
+        builder: { types::CancelBuilder }
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_ASYNC_CANCEL;
+
+    pub fn build(self) -> Entry {
+        let AsyncCancel2 { builder } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = builder.to_fd();
+        sqe.__bindgen_anon_2.addr = builder.user_data.unwrap_or(0);
+        sqe.__bindgen_anon_3.cancel_flags = builder.flags.bits();
+        Entry(sqe)
+    }
+);
+
 opcode!(
     /// A file/device-specific 16-byte command, akin (but not equivalent) to `ioctl(2)`.
     pub struct UringCmd16 {
@@ -1756,3 +2079,380 @@ opcode!(
         Entry(sqe)
     }
 );
+
+// === 6.7 ===
+
+opcode!(
+    /// Issue multiple reads from a file, like [`Read`] with the buffer picked by the kernel for
+    /// each read.
+    ///
+    /// Parameter:
+    ///     buf_group: The id of the provided buffer pool to use for each read.
+    ///
+    /// The multishot version allows the application to issue a single read request, which
+    /// repeatedly posts a CQE when data is available. It is meant for files that can be polled,
+    /// such as pipes, eventfds, timerfds and ttys. Each CQE will take a buffer out of a provided
+    /// buffer pool for reading, as reported by [`cqueue::buffer_select`](crate::cqueue::buffer_select).
+    /// The application should check the flags of each CQE, regardless of its result. If a posted
+    /// CQE does not have the IORING_CQE_F_MORE flag set, see [`cqueue::more`](crate::cqueue::more),
+    /// then the multishot read will be done and the application should issue a new request. This
+    /// happens on error, on end of file, or when the buffer pool is empty.
+    pub struct ReadMulti {
+        fd: { impl sealed::UseFixed },
+        buf_group: { u16 },
+        ;;
+        /// The maximum number of bytes read by each read, which is otherwise the length of the
+        /// selected buffer.
+        len: u32 = 0,
+        /// The offset to read from, which must be left to zero for files that cannot seek.
+        offset: u64 = 0,
+    }
+
+    pub const CODE = sys::IORING_OP_READ_MULTISHOT;
+
+    pub fn build(self) -> Entry {
+        let ReadMulti { fd, buf_group, len, offset } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.len = len;
+        sqe.__bindgen_anon_1.off = offset;
+        sqe.__bindgen_anon_4.buf_group = buf_group;
+        sqe.flags |= 1 << sys::IOSQE_BUFFER_SELECT_BIT;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Wait for a child process to change state, equivalent to `waitid(2)`.
+    ///
+    /// The `idtype`, `id` and `options` arguments have the same meaning as for `waitid(2)`, and
+    /// `infop`, if not null, is filled in with the state change of the child. The request
+    /// completes with `0` on success.
+    #[derive(Debug)]
+    pub struct WaitId {
+        idtype: { libc::idtype_t },
+        id: { libc::id_t },
+        options: { libc::c_int },
+        infop: { *mut libc::siginfo_t },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_WAITID;
+
+    pub fn build(self) -> Entry {
+        let WaitId { idtype, id, options, infop } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = id as _;
+        sqe.len = idtype as _;
+        sqe.__bindgen_anon_5.file_index = options as _;
+        sqe.__bindgen_anon_1.addr2 = infop as _;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Wait on a futex, like but not equivalent to `futex(2)`'s `FUTEX_WAIT_BITSET`.
+    ///
+    /// Wait on a futex at address `futex` and which still has the value `val` and with `futex2(2)`
+    /// flags of `futex_flags`. `mask` can be set to a specific bitset mask, which will be matched
+    /// by the waking side to decide who to wake up. To always get woken, an application may use
+    /// `FUTEX_BITSET_MATCH_ANY` (`u32::MAX`). `futex_flags` follows the `futex2(2)` flags, not
+    /// the `futex(2)` v1 interface flags, e.g. `FUTEX2_SIZE_U32 | FUTEX2_PRIVATE`.
+    ///
+    /// The request completes with `0` once woken, or `-EAGAIN` if the futex did not hold
+    /// `val` when the request was issued.
+    #[derive(Debug)]
+    pub struct FutexWait {
+        futex: { *const u32 },
+        val: { u64 },
+        mask: { u64 },
+        futex_flags: { u32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_FUTEX_WAIT;
+
+    pub fn build(self) -> Entry {
+        let FutexWait { futex, val, mask, futex_flags } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = futex_flags as _;
+        sqe.__bindgen_anon_2.addr = futex as usize as _;
+        sqe.__bindgen_anon_1.off = val;
+        unsafe { sqe.__bindgen_anon_6.__bindgen_anon_1.as_mut().addr3 = mask };
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Wake up waiters on a futex, like but not equivalent to `futex(2)`'s `FUTEX_WAKE_BITSET`.
+    ///
+    /// Wake any waiters on the futex indicated by `futex` and at most `val` futexes. `futex_flags`
+    /// indicates the `futex2(2)` modifier flags. If a given bitset for who to wake is desired,
+    /// then that must be set in `mask`. Use `FUTEX_BITSET_MATCH_ANY` (`u32::MAX`) to match any
+    /// waiter on the given futex.
+    ///
+    /// The request completes with the number of waiters that were woken up.
+    #[derive(Debug)]
+    pub struct FutexWake {
+        futex: { *const u32 },
+        val: { u64 },
+        mask: { u64 },
+        futex_flags: { u32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_FUTEX_WAKE;
+
+    pub fn build(self) -> Entry {
+        let FutexWake { futex, val, mask, futex_flags } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = futex_flags as _;
+        sqe.__bindgen_anon_2.addr = futex as usize as _;
+        sqe.__bindgen_anon_1.off = val;
+        unsafe { sqe.__bindgen_anon_6.__bindgen_anon_1.as_mut().addr3 = mask };
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Wait on multiple futexes, equivalent to `futex_waitv(2)`.
+    ///
+    /// Wait on the `nr_futex` futexes pointed to by `futexv`, each with its own expected value
+    /// and `futex2(2)` flags. The request completes with the index of the futex that woke it up.
+    #[derive(Debug)]
+    pub struct FutexWaitV {
+        futexv: { *const types::FutexWaitV },
+        nr_futex: { u32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_FUTEX_WAITV;
+
+    pub fn build(self) -> Entry {
+        let FutexWaitV { futexv, nr_futex } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.__bindgen_anon_2.addr = futexv as usize as _;
+        sqe.len = nr_futex;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Set a socket option, equivalent to `setsockopt(2)`.
+    ///
+    /// This is a [`UringCmd16`] command, which also works with sockets that only exist as
+    /// [`Fixed`](types::Fixed) files.
+    pub struct SetSockOpt {
+        fd: { impl sealed::UseFixed },
+        level: { u32 },
+        optname: { u32 },
+        optval: { *const libc::c_void },
+        optlen: { u32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_URING_CMD;
+
+    pub fn build(self) -> Entry {
+        let SetSockOpt { fd, level, optname, optval, optlen } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_1.__bindgen_anon_1.cmd_op = sys::SOCKET_URING_OP_SETSOCKOPT;
+        sqe.__bindgen_anon_2.__bindgen_anon_1.level = level;
+        sqe.__bindgen_anon_2.__bindgen_anon_1.optname = optname;
+        sqe.__bindgen_anon_5.optlen = optlen;
+        unsafe { sqe.__bindgen_anon_6.__bindgen_anon_1.as_mut().addr3 = optval as _ };
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Get a socket option, equivalent to `getsockopt(2)`.
+    ///
+    /// The request completes with the length of the option value written to `optval`. The kernel
+    /// only supports `SOL_SOCKET` options for this command.
+    ///
+    /// This is a [`UringCmd16`] command, which also works with sockets that only exist as
+    /// [`Fixed`](types::Fixed) files.
+    pub struct GetSockOpt {
+        fd: { impl sealed::UseFixed },
+        level: { u32 },
+        optname: { u32 },
+        optval: { *mut libc::c_void },
+        optlen: { u32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_URING_CMD;
+
+    pub fn build(self) -> Entry {
+        let GetSockOpt { fd, level, optname, optval, optlen } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_1.__bindgen_anon_1.cmd_op = sys::SOCKET_URING_OP_GETSOCKOPT;
+        sqe.__bindgen_anon_2.__bindgen_anon_1.level = level;
+        sqe.__bindgen_anon_2.__bindgen_anon_1.optname = optname;
+        sqe.__bindgen_anon_5.optlen = optlen;
+        unsafe { sqe.__bindgen_anon_6.__bindgen_anon_1.as_mut().addr3 = optval as _ };
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Get the amount of unread data in a socket's receive queue, equivalent to `ioctl(2)` with
+    /// `SIOCINQ`.
+    ///
+    /// The request completes with the number of bytes.
+    pub struct SiocInq {
+        fd: { impl sealed::UseFixed },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_URING_CMD;
+
+    pub fn build(self) -> Entry {
+        let SiocInq { fd } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_1.__bindgen_anon_1.cmd_op = sys::SOCKET_URING_OP_SIOCINQ;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Get the amount of unsent data in a socket's send queue, equivalent to `ioctl(2)` with
+    /// `SIOCOUTQ`.
+    ///
+    /// The request completes with the number of bytes.
+    pub struct SiocOutq {
+        fd: { impl sealed::UseFixed },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_URING_CMD;
+
+    pub fn build(self) -> Entry {
+        let SiocOutq { fd } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_1.__bindgen_anon_1.cmd_op = sys::SOCKET_URING_OP_SIOCOUTQ;
+        Entry(sqe)
+    }
+);
+
+// === 6.8 ===
+
+opcode!(
+    /// Install a fixed file into the process file table, returning a regular file descriptor
+    /// referring to the same file.
+    ///
+    /// The request completes with the new file descriptor. The fixed file is left registered.
+    pub struct FixedFdInstall {
+        fd: { types::Fixed },
+        ;;
+        /// Whether the new file descriptor has `O_CLOEXEC` set, the default.
+        cloexec: bool = true
+    }
+
+    pub const CODE = sys::IORING_OP_FIXED_FD_INSTALL;
+
+    pub fn build(self) -> Entry {
+        let FixedFdInstall { fd, cloexec } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        sqe.fd = fd.0 as _;
+        sqe.flags = crate::squeue::Flags::FIXED_FILE.bits();
+        if !cloexec {
+            sqe.__bindgen_anon_3.install_fd_flags = sys::IORING_FIXED_FD_NO_CLOEXEC;
+        }
+        Entry(sqe)
+    }
+);
+
+// === 6.9 ===
+
+opcode!(
+    /// Truncate a file to a specified length, equivalent to `ftruncate(2)`.
+    pub struct Ftruncate {
+        fd: { impl sealed::UseFixed },
+        len: { u64 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_FTRUNCATE;
+
+    pub fn build(self) -> Entry {
+        let Ftruncate { fd, len } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_1.off = len;
+        Entry(sqe)
+    }
+);
+
+// === 6.11 ===
+
+opcode!(
+    /// Bind a socket to an address, equivalent to `bind(2)`.
+    pub struct Bind {
+        fd: { impl sealed::UseFixed },
+        addr: { *const libc::sockaddr },
+        addrlen: { libc::socklen_t }
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_BIND;
+
+    pub fn build(self) -> Entry {
+        let Bind { fd, addr, addrlen } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.__bindgen_anon_2.addr = addr as _;
+        sqe.__bindgen_anon_1.off = addrlen as _;
+        Entry(sqe)
+    }
+);
+
+opcode!(
+    /// Listen for connections on a socket, equivalent to `listen(2)`.
+    pub struct Listen {
+        fd: { impl sealed::UseFixed },
+        backlog: { i32 },
+        ;;
+    }
+
+    pub const CODE = sys::IORING_OP_LISTEN;
+
+    pub fn build(self) -> Entry {
+        let Listen { fd, backlog } = self;
+
+        let mut sqe = sqe_zeroed();
+        sqe.opcode = Self::CODE;
+        assign_fd!(sqe.fd = fd);
+        sqe.len = backlog as _;
+        Entry(sqe)
+    }
+);
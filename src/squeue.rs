@@ -138,9 +138,12 @@ impl<E: EntryMarker> Inner<E> {
 
         let sqes         = sqe_mmap.as_mut_ptr() as *mut E;
 
-        // To keep it simple, map it directly to `sqes`.
-        for i in 0..ring_entries {
-            array.add(i as usize).write_volatile(i);
+        // To keep it simple, map it directly to `sqes`. Without the array, the kernel indexes
+        // `sqes` with the ring position itself, which is the same mapping.
+        if p.flags & sys::IORING_SETUP_NO_SQARRAY == 0 {
+            for i in 0..ring_entries {
+                array.add(i as usize).write_volatile(i);
+            }
         }
 
         Self {
@@ -1,5 +1,7 @@
+use std::mem::size_of;
 use std::os::unix::io::{AsRawFd, RawFd};
-use std::sync::atomic;
+use std::sync::atomic::{self, AtomicI32, AtomicUsize};
+use std::sync::{Arc, Mutex, Weak};
 use std::{io, ptr};
 
 use crate::register::{execute, Probe};
@@ -9,7 +11,107 @@ use crate::Parameters;
 
 use crate::register::Restriction;
 
-use crate::types;
+use crate::types::{self, BufRingFlags, CancelBuilder, Timespec};
+
+/// The ring fd registered with [`Submitter::register_ring_fd`].
+///
+/// The kernel keeps registered ring fds in a table of the registering thread, so the index is only
+/// meaningful on that thread, and only read by it. The kernel drops the registration when that
+/// thread exits, after which another thread can register the ring fd again.
+pub(crate) struct RegisteredRingFd {
+    // The id of the thread that registered the ring fd, as returned by `thread_id`, or 0.
+    owner: AtomicUsize,
+    index: AtomicI32,
+    // Alive as long as the owner thread, see `thread_alive`. Changes of the owner are made with
+    // this lock held, so that checking whether the owner is alive and replacing it cannot race.
+    owner_alive: Mutex<Weak<()>>,
+}
+
+impl RegisteredRingFd {
+    pub(crate) fn new() -> RegisteredRingFd {
+        RegisteredRingFd {
+            owner: AtomicUsize::new(0),
+            index: AtomicI32::new(-1),
+            owner_alive: Mutex::new(Weak::new()),
+        }
+    }
+
+    /// The registered index, if the ring fd was registered by the calling thread.
+    #[inline]
+    pub(crate) fn get(&self) -> Option<u32> {
+        if self.owner.load(atomic::Ordering::Relaxed) != thread_id() {
+            return None;
+        }
+        match self.index.load(atomic::Ordering::Relaxed) {
+            -1 => None,
+            index => Some(index as _),
+        }
+    }
+
+    // Make the calling thread the owner, unless another thread that is still alive owns it.
+    fn claim(&self) -> io::Result<()> {
+        let me = thread_id();
+        // The thread is exiting, and would drop the registration right away.
+        if me == 0 {
+            return Err(io::Error::from_raw_os_error(libc::EINVAL));
+        }
+
+        let mut owner_alive = self
+            .owner_alive
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        if self.owner.load(atomic::Ordering::Relaxed) != 0 && owner_alive.upgrade().is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "ring fd is already registered",
+            ));
+        }
+
+        // Either there is no owner, or it exited and its registration went with it.
+        self.index.store(-1, atomic::Ordering::Relaxed);
+        self.owner.store(me, atomic::Ordering::Relaxed);
+        *owner_alive = thread_alive();
+
+        Ok(())
+    }
+
+    fn release(&self) {
+        let mut owner_alive = self
+            .owner_alive
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        self.index.store(-1, atomic::Ordering::Relaxed);
+        self.owner.store(0, atomic::Ordering::Relaxed);
+        *owner_alive = Weak::new();
+    }
+}
+
+thread_local! {
+    // A unique, never reused, non-zero id of the thread. Thread ids of the OS can be reused once a
+    // thread exits, which would let a new thread use a registration that no longer exists.
+    static THREAD_ID: usize = {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed)
+    };
+
+    // Dropped when the thread exits.
+    static THREAD_ALIVE: Arc<()> = Arc::new(());
+}
+
+// The id of the calling thread, or 0 while its thread locals are being destroyed.
+#[inline]
+fn thread_id() -> usize {
+    THREAD_ID.try_with(|id| *id).unwrap_or(0)
+}
+
+// A handle that can be upgraded as long as the calling thread is alive.
+fn thread_alive() -> Weak<()> {
+    THREAD_ALIVE
+        .try_with(Arc::downgrade)
+        .unwrap_or_else(|_| Weak::new())
+}
 
 /// Interface for submitting submission queue events in an io_uring instance to the kernel for
 /// executing and registering files or buffers with the instance.
@@ -19,6 +121,7 @@ use crate::types;
 pub struct Submitter<'a> {
     fd: &'a OwnedFd,
     params: &'a Parameters,
+    registered_ring_fd: &'a RegisteredRingFd,
 
     sq_head: *const atomic::AtomicU32,
     sq_tail: *const atomic::AtomicU32,
@@ -30,6 +133,7 @@ impl<'a> Submitter<'a> {
     pub(crate) const fn new(
         fd: &'a OwnedFd,
         params: &'a Parameters,
+        registered_ring_fd: &'a RegisteredRingFd,
         sq_head: *const atomic::AtomicU32,
         sq_tail: *const atomic::AtomicU32,
         sq_flags: *const atomic::AtomicU32,
@@ -37,12 +141,18 @@ impl<'a> Submitter<'a> {
         Submitter {
             fd,
             params,
+            registered_ring_fd,
             sq_head,
             sq_tail,
             sq_flags,
         }
     }
 
+    #[inline]
+    pub(crate) fn fd(&self) -> &'a OwnedFd {
+        self.fd
+    }
+
     #[inline]
     fn sq_len(&self) -> usize {
         unsafe {
@@ -77,6 +187,9 @@ impl<'a> Submitter<'a> {
     /// You will probably want to use a more high-level API such as
     /// [`submit`](Self::submit) or [`submit_and_wait`](Self::submit_and_wait).
     ///
+    /// If the ring fd has been registered with [`register_ring_fd`](Self::register_ring_fd) on
+    /// the calling thread, the registered index is passed instead of the ring fd.
+    ///
     /// # Safety
     ///
     /// This provides a raw interface so developer must ensure that parameters are correct.
@@ -84,22 +197,21 @@ impl<'a> Submitter<'a> {
         &self,
         to_submit: u32,
         min_complete: u32,
-        flag: u32,
+        mut flag: u32,
         arg: Option<&T>,
     ) -> io::Result<usize> {
         let arg = arg
             .map(|arg| cast_ptr(arg) as *const _)
             .unwrap_or_else(ptr::null);
         let size = std::mem::size_of::<T>();
-        sys::io_uring_enter(
-            self.fd.as_raw_fd(),
-            to_submit,
-            min_complete,
-            flag,
-            arg,
-            size,
-        )
-        .map(|res| res as _)
+        let fd = match self.registered_ring_fd.get() {
+            Some(index) => {
+                flag |= sys::IORING_ENTER_REGISTERED_RING;
+                index as _
+            }
+            None => self.fd.as_raw_fd(),
+        };
+        sys::io_uring_enter(fd, to_submit, min_complete, flag, arg, size).map(|res| res as _)
     }
 
     /// Submit all queued submission queue events to the kernel.
@@ -354,6 +466,75 @@ impl<'a> Submitter<'a> {
         Ok(ret as _)
     }
 
+    /// Variant of [`register_files`](Self::register_files)
+    /// with resource tagging.
+    ///
+    /// `tags` should be the same length as `fds` and contain the
+    /// tag value corresponding to the file at the same index.
+    ///
+    /// If a tag is zero, then tagging for this particular resource
+    /// (a file in this case) is disabled. Otherwise, after the
+    /// resource had been unregistered and it's not used anymore,
+    /// a CQE will be posted with `user_data` set to the specified
+    /// tag and all other fields zeroed.
+    ///
+    /// Each fd may be -1, in which case it is considered "sparse", and can be filled in later with
+    /// [`register_files_update_tag`](Self::register_files_update_tag). The tag of a sparse entry
+    /// must be zero.
+    ///
+    /// Available since Linux 5.13.
+    pub fn register_files_tags(&self, fds: &[RawFd], tags: &[u64]) -> io::Result<()> {
+        let rr = sys::io_uring_rsrc_register {
+            nr: fds.len().min(tags.len()) as _,
+            data: fds.as_ptr() as _,
+            tags: tags.as_ptr() as _,
+            ..Default::default()
+        };
+        let rr = cast_ptr::<sys::io_uring_rsrc_register>(&rr);
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_FILES2,
+            rr as *const _,
+            size_of::<sys::io_uring_rsrc_register>() as _,
+        )
+        .map(drop)
+    }
+
+    /// Variant of [`register_files_update`](Self::register_files_update)
+    /// with resource tagging.
+    ///
+    /// This can be used with files registered using
+    /// [`register_files_sparse`](Self::register_files_sparse),
+    /// [`register_files`](Self::register_files) or
+    /// [`register_files_tags`](Self::register_files_tags).
+    ///
+    /// See [`register_files_tags`](Self::register_files_tags)
+    /// for more information about resource tagging.
+    ///
+    /// Available since Linux 5.13.
+    pub fn register_files_update_tag(
+        &self,
+        offset: u32,
+        fds: &[RawFd],
+        tags: &[u64],
+    ) -> io::Result<usize> {
+        let rr = sys::io_uring_rsrc_update2 {
+            nr: fds.len().min(tags.len()) as _,
+            data: fds.as_ptr() as _,
+            tags: tags.as_ptr() as _,
+            offset,
+            ..Default::default()
+        };
+        let rr = cast_ptr::<sys::io_uring_rsrc_update2>(&rr);
+        let ret = execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_FILES_UPDATE2,
+            rr as *const _,
+            size_of::<sys::io_uring_rsrc_update2>() as _,
+        )?;
+        Ok(ret as _)
+    }
+
     /// Register an eventfd created by [`eventfd`](libc::eventfd) with the io_uring instance.
     pub fn register_eventfd(&self, eventfd: RawFd) -> io::Result<()> {
         execute(
@@ -521,6 +702,57 @@ impl<'a> Submitter<'a> {
         .map(drop)
     }
 
+    /// Set the CPU affinity of the io_uring worker threads to `cpu_set`, for instance to keep
+    /// them off cores reserved for latency-critical threads. With
+    /// [`setup_sqpoll`](crate::Builder::setup_sqpoll) this applies to the workers of the
+    /// polling thread instead of those of the calling thread.
+    ///
+    /// Available since 5.14.
+    pub fn register_iowq_aff(&self, cpu_set: &libc::cpu_set_t) -> io::Result<()> {
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_IOWQ_AFF,
+            cast_ptr::<libc::cpu_set_t>(cpu_set) as *const _,
+            (libc::CPU_SETSIZE / 8) as _,
+        )
+        .map(drop)
+    }
+
+    /// Set the CPU affinity of the io_uring worker threads to the given list of CPU ids,
+    /// see [`register_iowq_aff`](Self::register_iowq_aff).
+    ///
+    /// If a CPU id does not fit in a `libc::cpu_set_t`, the InvalidInput error is returned.
+    ///
+    /// Available since 5.14.
+    pub fn register_iowq_aff_cpus(&self, cpus: &[usize]) -> io::Result<()> {
+        let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &cpu in cpus {
+            if cpu >= libc::CPU_SETSIZE as usize {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "cpu id exceeds the size of cpu_set_t",
+                ));
+            }
+            unsafe { libc::CPU_SET(cpu, &mut cpu_set) };
+        }
+
+        self.register_iowq_aff(&cpu_set)
+    }
+
+    /// Undo [`register_iowq_aff`](Self::register_iowq_aff), letting the io_uring worker threads
+    /// run on any CPU again.
+    ///
+    /// Available since 5.14.
+    pub fn unregister_iowq_aff(&self) -> io::Result<()> {
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_UNREGISTER_IOWQ_AFF,
+            ptr::null(),
+            0,
+        )
+        .map(drop)
+    }
+
     /// Register buffer ring for provided buffers.
     ///
     /// Details can be found in the io_uring_register_buf_ring.3 man page.
@@ -534,6 +766,21 @@ impl<'a> Submitter<'a> {
         ring_addr: u64,
         ring_entries: u16,
         bgid: u16,
+    ) -> io::Result<()> {
+        self.register_buf_ring_with_flags(ring_addr, ring_entries, bgid, BufRingFlags::empty())
+    }
+
+    /// Register buffer ring for provided buffers, with the given [`BufRingFlags`].
+    ///
+    /// With [`BufRingFlags::MMAP`], `ring_addr` must be zero as the kernel allocates the ring.
+    ///
+    /// Available since 5.19, flags since 6.4.
+    pub fn register_buf_ring_with_flags(
+        &self,
+        ring_addr: u64,
+        ring_entries: u16,
+        bgid: u16,
+        flags: BufRingFlags,
     ) -> io::Result<()> {
         // The interface type for ring_entries is u32 but the same interface only allows a u16 for
         // the tail to be specified, so to try and avoid further confusion, we limit the
@@ -543,7 +790,7 @@ impl<'a> Submitter<'a> {
             ring_addr,
             ring_entries: ring_entries as _,
             bgid,
-            pad: 0,
+            flags: flags.bits(),
             resv: Default::default(),
         };
         let arg = cast_ptr::<sys::io_uring_buf_reg>(&arg);
@@ -564,7 +811,7 @@ impl<'a> Submitter<'a> {
             ring_addr: 0,
             ring_entries: 0,
             bgid,
-            pad: 0,
+            flags: 0,
             resv: Default::default(),
         };
         let arg = cast_ptr::<sys::io_uring_buf_reg>(&arg);
@@ -576,4 +823,180 @@ impl<'a> Submitter<'a> {
         )
         .map(drop)
     }
+
+    /// Synchronously cancel requests matching the criteria of a [`CancelBuilder`], similar to
+    /// [`AsyncCancel2`](crate::opcode::AsyncCancel2) but waiting until the matched requests have
+    /// really been cancelled.
+    ///
+    /// Only requests that have already been submitted to the kernel are considered. If `timeout`
+    /// is `None`, this waits indefinitely.
+    ///
+    /// If no request matched, an error of kind [`NotFound`](io::ErrorKind::NotFound) is
+    /// returned. If a matched request is already running and cannot be cancelled, an error of
+    /// kind [`Other`](io::ErrorKind::Other) is returned. If the `timeout` elapsed before every
+    /// matched request was cancelled, an error of kind [`TimedOut`](io::ErrorKind::TimedOut) is
+    /// returned. The last two wrap the OS error, which [`get_ref`](io::Error::get_ref) gives
+    /// access to.
+    ///
+    /// Available since 6.0.
+    pub fn register_sync_cancel(
+        &self,
+        timeout: Option<Timespec>,
+        builder: CancelBuilder,
+    ) -> io::Result<()> {
+        let arg = sys::io_uring_sync_cancel_reg {
+            addr: builder.user_data.unwrap_or(0),
+            fd: builder.to_fd(),
+            flags: builder.flags.bits(),
+            timeout: timeout.map(|ts| ts.0).unwrap_or(sys::__kernel_timespec {
+                tv_sec: -1,
+                tv_nsec: -1,
+            }),
+            pad: Default::default(),
+        };
+        let arg = cast_ptr::<sys::io_uring_sync_cancel_reg>(&arg);
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_SYNC_CANCEL,
+            arg as *const _,
+            1,
+        )
+        .map(drop)
+        .map_err(|err| {
+            let kind = match err.raw_os_error() {
+                Some(libc::ETIME) => io::ErrorKind::TimedOut,
+                Some(libc::EALREADY) => io::ErrorKind::Other,
+                _ => return err,
+            };
+            io::Error::new(kind, err)
+        })
+    }
+
+    /// Restrict the range of the fixed file table in which the kernel allocates slots for
+    /// direct descriptors, for instance with
+    /// [`DestinationSlot::auto_target`](crate::types::DestinationSlot::auto_target) or
+    /// [`AcceptMulti::allocate_file_index`](crate::opcode::AcceptMulti::allocate_file_index).
+    /// Allocation is confined to the `len` slots starting at `offset`, leaving the remaining slots
+    /// to be managed by the application.
+    ///
+    /// Available since 6.0.
+    pub fn register_file_alloc_range(&self, offset: u32, len: u32) -> io::Result<()> {
+        let arg = sys::io_uring_file_index_range {
+            off: offset,
+            len,
+            resv: 0,
+        };
+        let arg = cast_ptr::<sys::io_uring_file_index_range>(&arg);
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_FILE_ALLOC_RANGE,
+            arg as *const _,
+            0,
+        )
+        .map(drop)
+    }
+
+    /// Busy poll the NAPI contexts of the network devices behind the sockets used by the ring,
+    /// with the settings of `napi`, when waiting for completions. Returns the previous settings.
+    ///
+    /// This requires a kernel built with `CONFIG_NET_RX_BUSY_POLL`, and fails with `EOPNOTSUPP`
+    /// otherwise.
+    ///
+    /// Available since 6.9, and fails with `EINVAL` on earlier kernels.
+    pub fn register_napi(&self, napi: &types::Napi) -> io::Result<types::Napi> {
+        let mut arg = napi.0;
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_NAPI,
+            &mut arg as *mut sys::io_uring_napi as *const _,
+            1,
+        )?;
+        Ok(types::Napi(arg))
+    }
+
+    /// Stop busy polling the NAPI contexts registered with
+    /// [`register_napi`](Self::register_napi). Returns the settings that were in effect.
+    ///
+    /// Available since 6.9.
+    pub fn unregister_napi(&self) -> io::Result<types::Napi> {
+        let mut arg = types::Napi::new().0;
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_UNREGISTER_NAPI,
+            &mut arg as *mut sys::io_uring_napi as *const _,
+            1,
+        )?;
+        Ok(types::Napi(arg))
+    }
+
+    /// Register the ring fd with the kernel, so that entering the ring skips looking up the ring
+    /// file on every call. Once registered, [`submit`](Self::submit),
+    /// [`submit_and_wait`](Self::submit_and_wait) and
+    /// [`submit_with_args`](Self::submit_with_args) automatically use the registered index.
+    /// Returns the registered index.
+    ///
+    /// The registration belongs to the calling thread. Other threads keep entering the ring
+    /// through its fd, and only the registering thread can
+    /// [`unregister_ring_fd`](Self::unregister_ring_fd). A single registration can exist at a
+    /// time, further calls fail with [`AlreadyExists`](io::ErrorKind::AlreadyExists) until it is
+    /// unregistered or the registering thread exits, which drops the registration.
+    ///
+    /// Available since 5.18.
+    pub fn register_ring_fd(&self) -> io::Result<u32> {
+        self.registered_ring_fd.claim()?;
+
+        let mut arg = sys::io_uring_rsrc_update {
+            offset: u32::MAX,
+            resv: 0,
+            data: self.fd.as_raw_fd() as _,
+        };
+
+        let res = execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_REGISTER_RING_FDS,
+            &mut arg as *mut sys::io_uring_rsrc_update as *const _,
+            1,
+        );
+        if let Err(err) = res {
+            self.registered_ring_fd.release();
+            return Err(err);
+        }
+
+        self.registered_ring_fd
+            .index
+            .store(arg.offset as _, atomic::Ordering::Relaxed);
+
+        Ok(arg.offset)
+    }
+
+    /// Unregister the ring fd registered with [`register_ring_fd`](Self::register_ring_fd),
+    /// going back to entering the ring through its fd.
+    ///
+    /// This must be called on the thread that registered the ring fd, and fails with `EINVAL`
+    /// otherwise.
+    ///
+    /// Available since 5.18.
+    pub fn unregister_ring_fd(&self) -> io::Result<()> {
+        let index = match self.registered_ring_fd.get() {
+            Some(index) => index,
+            None => return Err(io::Error::from_raw_os_error(libc::EINVAL)),
+        };
+
+        let arg = sys::io_uring_rsrc_update {
+            offset: index,
+            resv: 0,
+            data: 0,
+        };
+
+        execute(
+            self.fd.as_raw_fd(),
+            sys::IORING_UNREGISTER_RING_FDS,
+            cast_ptr::<sys::io_uring_rsrc_update>(&arg) as *const _,
+            1,
+        )?;
+
+        self.registered_ring_fd.release();
+
+        Ok(())
+    }
 }
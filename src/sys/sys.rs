@@ -91,6 +91,9 @@ pub const IORING_SETUP_SQE128: u32 = 1024;
 pub const IORING_SETUP_CQE32: u32 = 2048;
 pub const IORING_SETUP_SINGLE_ISSUER: u32 = 4096;
 pub const IORING_SETUP_DEFER_TASKRUN: u32 = 8192;
+pub const IORING_SETUP_NO_MMAP: u32 = 16384;
+pub const IORING_SETUP_REGISTERED_FD_ONLY: u32 = 32768;
+pub const IORING_SETUP_NO_SQARRAY: u32 = 65536;
 pub const IORING_URING_CMD_FIXED: u32 = 1;
 pub const IORING_FSYNC_DATASYNC: u32 = 1;
 pub const IORING_TIMEOUT_ABS: u32 = 1;
@@ -114,16 +117,22 @@ pub const IORING_RECVSEND_POLL_FIRST: u32 = 1;
 pub const IORING_RECV_MULTISHOT: u32 = 2;
 pub const IORING_RECVSEND_FIXED_BUF: u32 = 4;
 pub const IORING_SEND_ZC_REPORT_USAGE: u32 = 8;
+pub const IORING_RECVSEND_BUNDLE: u32 = 16;
 pub const IORING_NOTIF_USAGE_ZC_COPIED: u32 = 2147483648;
 pub const IORING_ACCEPT_MULTISHOT: u32 = 1;
 pub const IORING_MSG_RING_CQE_SKIP: u32 = 1;
+pub const IORING_FIXED_FD_NO_CLOEXEC: u32 = 1;
 pub const IORING_CQE_F_BUFFER: u32 = 1;
 pub const IORING_CQE_F_MORE: u32 = 2;
 pub const IORING_CQE_F_SOCK_NONEMPTY: u32 = 4;
 pub const IORING_CQE_F_NOTIF: u32 = 8;
+pub const IORING_CQE_F_BUF_MORE: u32 = 16;
 pub const IORING_OFF_SQ_RING: u32 = 0;
 pub const IORING_OFF_CQ_RING: u32 = 134217728;
 pub const IORING_OFF_SQES: u32 = 268435456;
+pub const IORING_OFF_PBUF_RING: u32 = 2147483648;
+pub const IORING_OFF_PBUF_SHIFT: u32 = 16;
+pub const IORING_OFF_MMAP_MASK: u32 = 4160749568;
 pub const IORING_SQ_NEED_WAKEUP: u32 = 1;
 pub const IORING_SQ_CQ_OVERFLOW: u32 = 2;
 pub const IORING_SQ_TASKRUN: u32 = 4;
@@ -146,6 +155,7 @@ pub const IORING_FEAT_NATIVE_WORKERS: u32 = 512;
 pub const IORING_FEAT_RSRC_TAGS: u32 = 1024;
 pub const IORING_FEAT_CQE_SKIP: u32 = 2048;
 pub const IORING_FEAT_LINKED_FILE: u32 = 4096;
+pub const IORING_FEAT_RECVSEND_BUNDLE: u32 = 16384;
 pub const IORING_RSRC_REGISTER_SPARSE: u32 = 1;
 pub const IORING_REGISTER_FILES_SKIP: i32 = -2;
 pub const IO_URING_OP_SUPPORTED: u32 = 1;
@@ -370,6 +380,55 @@ impl Default for io_uring_sqe__bindgen_ty_1 {
 pub union io_uring_sqe__bindgen_ty_2 {
     pub addr: __u64,
     pub splice_off_in: __u64,
+    pub __bindgen_anon_1: io_uring_sqe__bindgen_ty_2__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_sqe__bindgen_ty_2__bindgen_ty_1 {
+    pub level: __u32,
+    pub optname: __u32,
+}
+#[test]
+fn bindgen_test_layout_io_uring_sqe__bindgen_ty_2__bindgen_ty_1() {
+    const UNINIT: ::core::mem::MaybeUninit<io_uring_sqe__bindgen_ty_2__bindgen_ty_1> =
+        ::core::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::core::mem::size_of::<io_uring_sqe__bindgen_ty_2__bindgen_ty_1>(),
+        8usize,
+        concat!(
+            "Size of: ",
+            stringify!(io_uring_sqe__bindgen_ty_2__bindgen_ty_1)
+        )
+    );
+    assert_eq!(
+        ::core::mem::align_of::<io_uring_sqe__bindgen_ty_2__bindgen_ty_1>(),
+        4usize,
+        concat!(
+            "Alignment of ",
+            stringify!(io_uring_sqe__bindgen_ty_2__bindgen_ty_1)
+        )
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).level) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_sqe__bindgen_ty_2__bindgen_ty_1),
+            "::",
+            stringify!(level)
+        )
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).optname) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_sqe__bindgen_ty_2__bindgen_ty_1),
+            "::",
+            stringify!(optname)
+        )
+    );
 }
 #[test]
 fn bindgen_test_layout_io_uring_sqe__bindgen_ty_2() {
@@ -438,6 +497,7 @@ pub union io_uring_sqe__bindgen_ty_3 {
     pub xattr_flags: __u32,
     pub msg_ring_flags: __u32,
     pub uring_cmd_flags: __u32,
+    pub install_fd_flags: __u32,
 }
 #[test]
 fn bindgen_test_layout_io_uring_sqe__bindgen_ty_3() {
@@ -644,6 +704,16 @@ fn bindgen_test_layout_io_uring_sqe__bindgen_ty_3() {
             stringify!(uring_cmd_flags)
         )
     );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).install_fd_flags) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_sqe__bindgen_ty_3),
+            "::",
+            stringify!(install_fd_flags)
+        )
+    );
 }
 impl Default for io_uring_sqe__bindgen_ty_3 {
     fn default() -> Self {
@@ -710,6 +780,7 @@ impl Default for io_uring_sqe__bindgen_ty_4 {
 pub union io_uring_sqe__bindgen_ty_5 {
     pub splice_fd_in: __s32,
     pub file_index: __u32,
+    pub optlen: __u32,
     pub __bindgen_anon_1: io_uring_sqe__bindgen_ty_5__bindgen_ty_1,
 }
 #[repr(C)]
@@ -795,6 +866,16 @@ fn bindgen_test_layout_io_uring_sqe__bindgen_ty_5() {
             stringify!(file_index)
         )
     );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).optlen) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_sqe__bindgen_ty_5),
+            "::",
+            stringify!(optlen)
+        )
+    );
 }
 impl Default for io_uring_sqe__bindgen_ty_5 {
     fn default() -> Self {
@@ -1045,7 +1126,16 @@ pub const IORING_OP_SOCKET: io_uring_op = 45;
 pub const IORING_OP_URING_CMD: io_uring_op = 46;
 pub const IORING_OP_SEND_ZC: io_uring_op = 47;
 pub const IORING_OP_SENDMSG_ZC: io_uring_op = 48;
-pub const IORING_OP_LAST: io_uring_op = 49;
+pub const IORING_OP_READ_MULTISHOT: io_uring_op = 49;
+pub const IORING_OP_WAITID: io_uring_op = 50;
+pub const IORING_OP_FUTEX_WAIT: io_uring_op = 51;
+pub const IORING_OP_FUTEX_WAKE: io_uring_op = 52;
+pub const IORING_OP_FUTEX_WAITV: io_uring_op = 53;
+pub const IORING_OP_FIXED_FD_INSTALL: io_uring_op = 54;
+pub const IORING_OP_FTRUNCATE: io_uring_op = 55;
+pub const IORING_OP_BIND: io_uring_op = 56;
+pub const IORING_OP_LISTEN: io_uring_op = 57;
+pub const IORING_OP_LAST: io_uring_op = 58;
 pub type io_uring_op = libc::c_uint;
 pub const IORING_MSG_DATA: _bindgen_ty_5 = 0;
 pub const IORING_MSG_SEND_FD: _bindgen_ty_5 = 1;
@@ -1126,7 +1216,7 @@ pub struct io_sqring_offsets {
     pub dropped: __u32,
     pub array: __u32,
     pub resv1: __u32,
-    pub resv2: __u64,
+    pub user_addr: __u64,
 }
 #[test]
 fn bindgen_test_layout_io_sqring_offsets() {
@@ -1223,13 +1313,13 @@ fn bindgen_test_layout_io_sqring_offsets() {
         )
     );
     assert_eq!(
-        unsafe { ::core::ptr::addr_of!((*ptr).resv2) as usize - ptr as usize },
+        unsafe { ::core::ptr::addr_of!((*ptr).user_addr) as usize - ptr as usize },
         32usize,
         concat!(
             "Offset of field: ",
             stringify!(io_sqring_offsets),
             "::",
-            stringify!(resv2)
+            stringify!(user_addr)
         )
     );
 }
@@ -1244,7 +1334,7 @@ pub struct io_cqring_offsets {
     pub cqes: __u32,
     pub flags: __u32,
     pub resv1: __u32,
-    pub resv2: __u64,
+    pub user_addr: __u64,
 }
 #[test]
 fn bindgen_test_layout_io_cqring_offsets() {
@@ -1341,13 +1431,13 @@ fn bindgen_test_layout_io_cqring_offsets() {
         )
     );
     assert_eq!(
-        unsafe { ::core::ptr::addr_of!((*ptr).resv2) as usize - ptr as usize },
+        unsafe { ::core::ptr::addr_of!((*ptr).user_addr) as usize - ptr as usize },
         32usize,
         concat!(
             "Offset of field: ",
             stringify!(io_cqring_offsets),
             "::",
-            stringify!(resv2)
+            stringify!(user_addr)
         )
     );
 }
@@ -1506,7 +1596,14 @@ pub const IORING_REGISTER_PBUF_RING: _bindgen_ty_7 = 22;
 pub const IORING_UNREGISTER_PBUF_RING: _bindgen_ty_7 = 23;
 pub const IORING_REGISTER_SYNC_CANCEL: _bindgen_ty_7 = 24;
 pub const IORING_REGISTER_FILE_ALLOC_RANGE: _bindgen_ty_7 = 25;
-pub const IORING_REGISTER_LAST: _bindgen_ty_7 = 26;
+pub const IORING_REGISTER_PBUF_STATUS: _bindgen_ty_7 = 26;
+pub const IORING_REGISTER_NAPI: _bindgen_ty_7 = 27;
+pub const IORING_UNREGISTER_NAPI: _bindgen_ty_7 = 28;
+pub const IORING_REGISTER_CLOCK: _bindgen_ty_7 = 29;
+pub const IORING_REGISTER_CLONE_BUFFERS: _bindgen_ty_7 = 30;
+pub const IORING_REGISTER_SEND_MSG_RING: _bindgen_ty_7 = 31;
+pub const IORING_REGISTER_RESIZE_RINGS: _bindgen_ty_7 = 33;
+pub const IORING_REGISTER_LAST: _bindgen_ty_7 = 34;
 pub type _bindgen_ty_7 = libc::c_uint;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
@@ -2357,13 +2454,16 @@ impl Default for io_uring_buf_ring {
         }
     }
 }
+pub const IOU_PBUF_RING_MMAP: io_uring_register_pbuf_ring_flags = 1;
+pub const IOU_PBUF_RING_INC: io_uring_register_pbuf_ring_flags = 2;
+pub type io_uring_register_pbuf_ring_flags = libc::c_uint;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
 pub struct io_uring_buf_reg {
     pub ring_addr: __u64,
     pub ring_entries: __u32,
     pub bgid: __u16,
-    pub pad: __u16,
+    pub flags: __u16,
     pub resv: [__u64; 3usize],
 }
 #[test]
@@ -2411,13 +2511,13 @@ fn bindgen_test_layout_io_uring_buf_reg() {
         )
     );
     assert_eq!(
-        unsafe { ::core::ptr::addr_of!((*ptr).pad) as usize - ptr as usize },
+        unsafe { ::core::ptr::addr_of!((*ptr).flags) as usize - ptr as usize },
         14usize,
         concat!(
             "Offset of field: ",
             stringify!(io_uring_buf_reg),
             "::",
-            stringify!(pad)
+            stringify!(flags)
         )
     );
     assert_eq!(
@@ -2631,6 +2731,69 @@ fn bindgen_test_layout_io_uring_file_index_range() {
 }
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone)]
+pub struct io_uring_napi {
+    pub busy_poll_to: __u32,
+    pub prefer_busy_poll: __u8,
+    pub pad: [__u8; 3usize],
+    pub resv: __u64,
+}
+#[test]
+fn bindgen_test_layout_io_uring_napi() {
+    const UNINIT: ::core::mem::MaybeUninit<io_uring_napi> = ::core::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::core::mem::size_of::<io_uring_napi>(),
+        16usize,
+        concat!("Size of: ", stringify!(io_uring_napi))
+    );
+    assert_eq!(
+        ::core::mem::align_of::<io_uring_napi>(),
+        8usize,
+        concat!("Alignment of ", stringify!(io_uring_napi))
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).busy_poll_to) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_napi),
+            "::",
+            stringify!(busy_poll_to)
+        )
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).prefer_busy_poll) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_napi),
+            "::",
+            stringify!(prefer_busy_poll)
+        )
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).pad) as usize - ptr as usize },
+        5usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_napi),
+            "::",
+            stringify!(pad)
+        )
+    );
+    assert_eq!(
+        unsafe { ::core::ptr::addr_of!((*ptr).resv) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(io_uring_napi),
+            "::",
+            stringify!(resv)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
 pub struct io_uring_recvmsg_out {
     pub namelen: __u32,
     pub controllen: __u32,
@@ -2693,3 +2856,8 @@ fn bindgen_test_layout_io_uring_recvmsg_out() {
         )
     );
 }
+pub const SOCKET_URING_OP_SIOCINQ: io_uring_socket_op = 0;
+pub const SOCKET_URING_OP_SIOCOUTQ: io_uring_socket_op = 1;
+pub const SOCKET_URING_OP_GETSOCKOPT: io_uring_socket_op = 2;
+pub const SOCKET_URING_OP_SETSOCKOPT: io_uring_socket_op = 3;
+pub type io_uring_socket_op = libc::c_uint;
@@ -81,6 +81,8 @@ bitflags! {
         const ABS = sys::IORING_TIMEOUT_ABS;
 
         const UPDATE = sys::IORING_TIMEOUT_UPDATE;
+
+        const LINK_TIMEOUT_UPDATE = sys::IORING_LINK_TIMEOUT_UPDATE;
     }
 }
 
@@ -91,6 +93,53 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Options for [`AsyncCancel2`](crate::opcode::AsyncCancel2).
+    pub(crate) struct AsyncCancelFlags: u32 {
+        /// Cancel all requests that match the given criteria, rather than just the first one
+        /// found.
+        ///
+        /// Available since 5.19.
+        const ALL = sys::IORING_ASYNC_CANCEL_ALL;
+
+        /// Match based on the file descriptor used in the original request rather than the
+        /// user_data.
+        ///
+        /// Available since 5.19.
+        const FD = sys::IORING_ASYNC_CANCEL_FD;
+
+        /// Match any request in the ring, regardless of user_data or file descriptor.
+        ///
+        /// Available since 5.19.
+        const ANY = sys::IORING_ASYNC_CANCEL_ANY;
+
+        /// Match based on the fixed file descriptor used in the original request rather than the
+        /// user_data.
+        ///
+        /// Available since 6.0.
+        const FD_FIXED = sys::IORING_ASYNC_CANCEL_FD_FIXED;
+    }
+}
+
+bitflags! {
+    /// Options for [`Submitter::register_buf_ring_with_flags`](crate::Submitter::register_buf_ring_with_flags).
+    pub struct BufRingFlags: u16 {
+        /// Let the kernel allocate the ring, which the application then maps from the io_uring
+        /// file descriptor rather than passing its own `ring_addr`.
+        ///
+        /// Available since 6.4.
+        const MMAP = sys::IOU_PBUF_RING_MMAP as u16;
+
+        /// Consume buffers incrementally, so that a large buffer can be filled by several
+        /// requests, each one starting where the previous one stopped. Completions that leave
+        /// part of the buffer to later requests are flagged with
+        /// [`cqueue::buf_more`](crate::cqueue::buf_more).
+        ///
+        /// Available since 6.12.
+        const INC = sys::IOU_PBUF_RING_INC as u16;
+    }
+}
+
 /// Wrapper around `open_how` as used in [the `openat2(2)` system
 /// call](https://man7.org/linux/man-pages/man2/openat2.2.html).
 #[derive(Default, Debug, Clone, Copy)]
@@ -124,7 +173,7 @@ impl OpenHow {
 
 #[derive(Default, Debug, Clone, Copy)]
 #[repr(transparent)]
-pub struct Timespec(sys::__kernel_timespec);
+pub struct Timespec(pub(crate) sys::__kernel_timespec);
 
 impl Timespec {
     #[inline]
@@ -148,6 +197,97 @@ impl Timespec {
     }
 }
 
+/// Wrapper around `futex_waitv` as used in [the `futex_waitv(2)` system
+/// call](https://www.kernel.org/doc/html/latest/userspace-api/futex2.html), for
+/// [`FutexWaitV`](crate::opcode::FutexWaitV).
+#[derive(Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FutexWaitV {
+    val: u64,
+    uaddr: u64,
+    flags: u32,
+    __reserved: u32,
+}
+
+impl FutexWaitV {
+    #[inline]
+    pub const fn new() -> Self {
+        FutexWaitV {
+            val: 0,
+            uaddr: 0,
+            flags: 0,
+            __reserved: 0,
+        }
+    }
+
+    /// The value the futex is expected to hold, the wait fails with `EAGAIN` otherwise.
+    #[inline]
+    pub const fn val(mut self, val: u64) -> Self {
+        self.val = val;
+        self
+    }
+
+    /// The address of the futex.
+    #[inline]
+    pub const fn uaddr(mut self, uaddr: u64) -> Self {
+        self.uaddr = uaddr;
+        self
+    }
+
+    /// The `FUTEX2_*` flags of the futex, e.g. `FUTEX2_SIZE_U32 | FUTEX2_PRIVATE`.
+    #[inline]
+    pub const fn flags(mut self, flags: u32) -> Self {
+        self.flags = flags;
+        self
+    }
+}
+
+/// Wrapper around `io_uring_napi`, the NAPI busy poll settings of
+/// [`Submitter::register_napi`](crate::Submitter::register_napi).
+#[derive(Default, Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Napi(pub(crate) sys::io_uring_napi);
+
+impl Napi {
+    #[inline]
+    pub const fn new() -> Self {
+        Napi(sys::io_uring_napi {
+            busy_poll_to: 0,
+            prefer_busy_poll: 0,
+            pad: [0; 3],
+            resv: 0,
+        })
+    }
+
+    /// How long to busy poll the NAPI contexts of the sockets used by the ring, in
+    /// microseconds.
+    #[inline]
+    pub const fn busy_poll_timeout(mut self, busy_poll_timeout: u32) -> Self {
+        self.0.busy_poll_to = busy_poll_timeout;
+        self
+    }
+
+    /// Ask the network stack to prefer busy polling over interrupts, as with the
+    /// `SO_PREFER_BUSY_POLL` socket option.
+    #[inline]
+    pub const fn prefer_busy_poll(mut self, prefer_busy_poll: bool) -> Self {
+        self.0.prefer_busy_poll = prefer_busy_poll as _;
+        self
+    }
+
+    /// The busy poll timeout, in microseconds.
+    #[inline]
+    pub const fn get_busy_poll_timeout(&self) -> u32 {
+        self.0.busy_poll_to
+    }
+
+    /// Whether busy polling is preferred over interrupts.
+    #[inline]
+    pub const fn get_prefer_busy_poll(&self) -> bool {
+        self.0.prefer_busy_poll != 0
+    }
+}
+
 /// Submit arguments
 ///
 /// Note that arguments that exceed their lifetime will fail to compile.
@@ -331,6 +471,77 @@ impl DestinationSlot {
     }
 }
 
+/// The match criteria of a cancellation request, used by
+/// [`AsyncCancel2`](crate::opcode::AsyncCancel2).
+#[derive(Debug)]
+pub struct CancelBuilder {
+    pub(crate) flags: AsyncCancelFlags,
+    pub(crate) user_data: Option<u64>,
+    pub(crate) fd: Option<sealed::Target>,
+}
+
+impl CancelBuilder {
+    /// Match any in-flight request, regardless of its user_data or file descriptor.
+    ///
+    /// Available since 5.19.
+    pub const fn any() -> Self {
+        Self {
+            flags: AsyncCancelFlags::ANY,
+            user_data: None,
+            fd: None,
+        }
+    }
+
+    /// Match in-flight requests with the given `user_data`.
+    ///
+    /// Only the first matching request is cancelled, unless [`all`](Self::all) is also set.
+    pub const fn user_data(user_data: u64) -> Self {
+        Self {
+            flags: AsyncCancelFlags::empty(),
+            user_data: Some(user_data),
+            fd: None,
+        }
+    }
+
+    /// Match in-flight requests issued against the given file descriptor, either a [`Fd`] or a
+    /// [`Fixed`] slot.
+    ///
+    /// Only the first matching request is cancelled, unless [`all`](Self::all) is also set.
+    ///
+    /// Available since 5.19, matching on a [`Fixed`] slot since 6.0.
+    pub fn fd(fd: impl sealed::UseFixed) -> Self {
+        let fd = fd.into();
+        let flags = match fd {
+            sealed::Target::Fd(_) => AsyncCancelFlags::FD,
+            sealed::Target::Fixed(_) => AsyncCancelFlags::FD | AsyncCancelFlags::FD_FIXED,
+        };
+
+        Self {
+            flags,
+            user_data: None,
+            fd: Some(fd),
+        }
+    }
+
+    /// Cancel every request matching the criteria rather than only the first one found.
+    ///
+    /// This has no effect when combined with [`any`](Self::any).
+    ///
+    /// Available since 5.19.
+    pub fn all(mut self) -> Self {
+        self.flags.insert(AsyncCancelFlags::ALL);
+        self
+    }
+
+    pub(crate) fn to_fd(&self) -> RawFd {
+        match self.fd {
+            Some(sealed::Target::Fd(fd)) => fd,
+            Some(sealed::Target::Fixed(i)) => i as _,
+            None => -1,
+        }
+    }
+}
+
 /// Helper structure for parsing the result of a multishot [`opcode::RecvMsg`](crate::opcode::RecvMsg).
 #[derive(Debug)]
 pub struct RecvMsgOut<'buf> {
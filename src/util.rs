@@ -2,10 +2,17 @@ use std::os::unix::io::AsRawFd;
 use std::sync::atomic;
 use std::{io, ptr};
 
-/// A region of memory mapped using `mmap(2)`.
+// `MAP_HUGE_2MB`, which is not defined by older versions of `libc`.
+const MAP_HUGE_2MB: libc::c_int = 21 << 26;
+
+/// The size of the huge pages allocated by [`Mmap::new_anonymous_huge`].
+pub const HUGE_PAGE_SIZE: usize = 2 << 20;
+
+/// A region of memory mapped using `mmap(2)`, or borrowed from the application.
 pub struct Mmap {
     addr: ptr::NonNull<libc::c_void>,
     len: usize,
+    owned: bool,
 }
 
 impl Mmap {
@@ -24,12 +31,88 @@ impl Mmap {
                 addr => {
                     // here, `mmap` will never return null
                     let addr = ptr::NonNull::new_unchecked(addr);
-                    Ok(Mmap { addr, len })
+                    Ok(Mmap {
+                        addr,
+                        len,
+                        owned: true,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Allocate `len` bytes of anonymous memory, which is page-aligned and zero-filled.
+    pub fn new_anonymous(len: usize) -> io::Result<Mmap> {
+        unsafe {
+            match libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_ANONYMOUS | libc::MAP_SHARED | libc::MAP_POPULATE,
+                -1,
+                0,
+            ) {
+                libc::MAP_FAILED => Err(io::Error::last_os_error()),
+                addr => {
+                    // here, `mmap` will never return null
+                    let addr = ptr::NonNull::new_unchecked(addr);
+                    Ok(Mmap {
+                        addr,
+                        len,
+                        owned: true,
+                    })
                 }
             }
         }
     }
 
+    /// Allocate `len` bytes of anonymous memory backed by 2 MiB huge pages, which is zero-filled.
+    /// This fails unless huge pages of that size are reserved by the system.
+    pub fn new_anonymous_huge(len: usize) -> io::Result<Mmap> {
+        // Huge page mappings can only be unmapped as a whole.
+        let len = (len + HUGE_PAGE_SIZE - 1) & !(HUGE_PAGE_SIZE - 1);
+
+        unsafe {
+            match libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_ANONYMOUS
+                    | libc::MAP_SHARED
+                    | libc::MAP_POPULATE
+                    | libc::MAP_HUGETLB
+                    | MAP_HUGE_2MB,
+                -1,
+                0,
+            ) {
+                libc::MAP_FAILED => Err(io::Error::last_os_error()),
+                addr => {
+                    // here, `mmap` will never return null
+                    let addr = ptr::NonNull::new_unchecked(addr);
+                    Ok(Mmap {
+                        addr,
+                        len,
+                        owned: true,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Refer to `len` bytes of memory at `addr` that is owned elsewhere, and is not unmapped on
+    /// drop.
+    ///
+    /// # Safety
+    ///
+    /// The memory must be valid for reads and writes and outlive the returned value.
+    pub unsafe fn from_raw(addr: ptr::NonNull<libc::c_void>, len: usize) -> Mmap {
+        Mmap {
+            addr,
+            len,
+            owned: false,
+        }
+    }
+
     /// Do not make the stored memory accessible by child processes after a `fork`.
     pub fn dontfork(&self) -> io::Result<()> {
         match unsafe { libc::madvise(self.addr.as_ptr(), self.len, libc::MADV_DONTFORK) } {
@@ -53,8 +136,10 @@ impl Mmap {
 
 impl Drop for Mmap {
     fn drop(&mut self) {
-        unsafe {
-            libc::munmap(self.addr.as_ptr(), self.len);
+        if self.owned {
+            unsafe {
+                libc::munmap(self.addr.as_ptr(), self.len);
+            }
         }
     }
 }
@@ -68,12 +153,22 @@ mod fd {
 
 #[cfg(not(feature = "io_safety"))]
 mod fd {
-    use std::mem;
     use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+    use std::{io, mem};
 
     /// API-compatible with the `OwnedFd` type in the Rust stdlib.
     pub struct OwnedFd(RawFd);
 
+    impl OwnedFd {
+        /// Create a new `OwnedFd` instance that shares the same underlying file description.
+        pub fn try_clone(&self) -> io::Result<OwnedFd> {
+            match unsafe { libc::fcntl(self.0, libc::F_DUPFD_CLOEXEC, 0) } {
+                -1 => Err(io::Error::last_os_error()),
+                fd => Ok(OwnedFd(fd)),
+            }
+        }
+    }
+
     impl AsRawFd for OwnedFd {
         #[inline]
         fn as_raw_fd(&self) -> RawFd {